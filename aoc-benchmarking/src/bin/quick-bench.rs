@@ -0,0 +1,121 @@
+// link every day's crate so that its problem is present in the registry
+include!(concat!(env!("OUT_DIR"), "/days.rs"));
+
+use std::path::PathBuf;
+
+use anyhow::{bail, Context, Result};
+use aoc_benchmarking::quick::{self, Format, Summary};
+use aoc_plumbing::{registry, workspace, Part};
+use clap::Parser;
+
+/// Time each part of every registered day without criterion, and write a
+/// summary of the mean, median, p95 and min times in nanoseconds.
+///
+/// If a baseline is given, the median times are compared against it and any
+/// part that is slower by more than the threshold causes a non-zero exit.
+#[derive(Parser)]
+#[command(name = "quick-bench", version)]
+struct Args {
+    /// The number of times to solve each part.
+    #[clap(short = 'n', long, default_value_t = 10)]
+    iterations: usize,
+
+    /// Only time the given day(s).
+    #[clap(short, long)]
+    day: Vec<usize>,
+
+    /// Only time the given part (1 or 2).
+    #[clap(short, long)]
+    part: Option<Part>,
+
+    /// The directory containing the `day-NNN-*` directories.
+    #[clap(long, env = "AOC_ROOT", default_value = ".")]
+    root: PathBuf,
+
+    /// Write the summary to this file instead of stdout.
+    #[clap(short, long)]
+    output: Option<PathBuf>,
+
+    /// The format of the summary. Defaults to the output file's extension, or
+    /// json.
+    #[clap(short, long)]
+    format: Option<Format>,
+
+    /// A previously written summary (json or csv) to compare against.
+    #[clap(short, long)]
+    baseline: Option<PathBuf>,
+
+    /// The percentage increase in median time over the baseline that counts
+    /// as a regression.
+    #[clap(short, long, default_value_t = 10.0)]
+    threshold: f64,
+}
+
+fn main() -> Result<()> {
+    let args = Args::parse();
+
+    let parts = match args.part {
+        Some(part) => vec![part],
+        None => vec![Part::One, Part::Two],
+    };
+
+    let mut summaries: Vec<Summary> = Vec::default();
+    for problem in registry::problems() {
+        if !args.day.is_empty() && !args.day.contains(&problem.day()) {
+            continue;
+        }
+
+        let Some(path) = workspace::find_input(&args.root, problem.day())
+            .with_context(|| format!("Could not read directory {}", args.root.display()))?
+        else {
+            eprintln!("skipping {}: no input found", problem.problem_label());
+            continue;
+        };
+        let input = std::fs::read_to_string(&path)
+            .with_context(|| format!("Could not read input file {}", path.display()))?;
+
+        for part in parts.iter() {
+            eprintln!("timing {} part {}", problem.problem_label(), part);
+            summaries.push(quick::measure(problem, &input, *part, args.iterations)?);
+        }
+    }
+
+    let format = args.format.unwrap_or_else(|| {
+        args.output
+            .as_deref()
+            .map(Format::from_path)
+            .unwrap_or_default()
+    });
+    let out = format.write(&summaries)?;
+
+    match args.output {
+        Some(ref path) => std::fs::write(path, out)
+            .with_context(|| format!("Could not write summary to {}", path.display()))?,
+        None => println!("{}", out.trim_end()),
+    }
+
+    if let Some(ref path) = args.baseline {
+        let raw = std::fs::read_to_string(path)
+            .with_context(|| format!("Could not read baseline {}", path.display()))?;
+        let baseline = Format::from_path(path)
+            .read(&raw)
+            .with_context(|| format!("Could not parse baseline {}", path.display()))?;
+
+        let comparisons = quick::compare(&summaries, &baseline, args.threshold);
+        for c in comparisons.iter() {
+            eprintln!("{}", c);
+        }
+
+        let regressed = comparisons.iter().filter(|c| c.regressed).count();
+        if regressed > 0 {
+            bail!(
+                "{} of {} parts regressed by more than {}%",
+                regressed,
+                comparisons.len(),
+                args.threshold
+            );
+        }
+    }
+
+    Ok(())
+}
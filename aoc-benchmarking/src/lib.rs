@@ -1 +1,2 @@
 pub mod helper_macros;
+pub mod quick;
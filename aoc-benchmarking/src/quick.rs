@@ -0,0 +1,326 @@
+//! A lightweight alternative to the criterion benchmarks, for when only a
+//! summary of how long each part takes is needed.
+//!
+//! Each part of a problem is solved a fixed number of times, and the samples
+//! are reduced to a [`Summary`] that can be written as JSON or CSV. A saved
+//! set of summaries can then be used as a baseline for later runs, with
+//! [`compare`] flagging any parts that have slowed down by more than a given
+//! percentage.
+use std::{fmt::Display, path::Path, str::FromStr};
+
+use aoc_plumbing::{
+    registry::{DynError, DynProblem},
+    Part,
+};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, thiserror::Error)]
+pub enum QuickBenchError {
+    #[error("could not solve day {day} part {part}: {source}")]
+    Solve {
+        day: usize,
+        part: Part,
+        source: DynError,
+    },
+
+    #[error("no timing was recorded for day {day} part {part}")]
+    MissingTiming { day: usize, part: Part },
+
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+
+    #[error("invalid csv on line {line}: {message}")]
+    Csv { line: usize, message: String },
+
+    #[error("unknown format {0:?}, expected json or csv")]
+    UnknownFormat(String),
+}
+
+/// The timings for one part of one day, in nanoseconds.
+///
+/// Only the time taken to solve the part is measured; parsing is excluded, to
+/// match the per-part criterion benchmarks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Summary {
+    pub day: usize,
+    pub part: usize,
+    pub mean_ns: u64,
+    pub median_ns: u64,
+    pub p95_ns: u64,
+    pub min_ns: u64,
+}
+
+impl Summary {
+    /// Reduces the given samples to a summary. Returns `None` if there are no
+    /// samples.
+    ///
+    /// The median of an even number of samples is the lower of the two middle
+    /// samples, and the p95 uses the nearest-rank method.
+    ///
+    /// ```
+    /// use aoc_benchmarking::quick::Summary;
+    ///
+    /// let s = Summary::from_samples(3, 1, vec![40, 10, 30, 20]).unwrap();
+    ///
+    /// assert_eq!((s.mean_ns, s.median_ns, s.p95_ns, s.min_ns), (25, 20, 40, 10));
+    /// assert!(Summary::from_samples(3, 1, vec![]).is_none());
+    /// ```
+    pub fn from_samples(day: usize, part: usize, mut samples: Vec<u64>) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+
+        samples.sort_unstable();
+        let len = samples.len();
+        let sum: u128 = samples.iter().map(|&s| s as u128).sum();
+        let p95_rank = (len * 95).div_ceil(100).max(1);
+
+        Some(Self {
+            day,
+            part,
+            mean_ns: (sum / len as u128) as u64,
+            median_ns: samples[(len - 1) / 2],
+            p95_ns: samples[p95_rank - 1],
+            min_ns: samples[0],
+        })
+    }
+}
+
+/// The supported output formats for a set of summaries.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Format {
+    #[default]
+    Json,
+    Csv,
+}
+
+impl Format {
+    /// Guesses the format from a path's extension, defaulting to json.
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|e| e.to_str()) {
+            Some(e) if e.eq_ignore_ascii_case("csv") => Self::Csv,
+            _ => Self::Json,
+        }
+    }
+
+    pub fn write(&self, summaries: &[Summary]) -> Result<String, QuickBenchError> {
+        match self {
+            Self::Json => Ok(serde_json::to_string_pretty(summaries)?),
+            Self::Csv => Ok(to_csv(summaries)),
+        }
+    }
+
+    pub fn read(&self, raw: &str) -> Result<Vec<Summary>, QuickBenchError> {
+        match self {
+            Self::Json => Ok(serde_json::from_str(raw)?),
+            Self::Csv => from_csv(raw),
+        }
+    }
+}
+
+impl FromStr for Format {
+    type Err = QuickBenchError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "json" => Ok(Self::Json),
+            "csv" => Ok(Self::Csv),
+            _ => Err(QuickBenchError::UnknownFormat(s.to_string())),
+        }
+    }
+}
+
+const CSV_HEADER: &str = "day,part,mean_ns,median_ns,p95_ns,min_ns";
+
+fn to_csv(summaries: &[Summary]) -> String {
+    let mut out = String::from(CSV_HEADER);
+    out.push('\n');
+
+    for s in summaries.iter() {
+        out.push_str(&format!(
+            "{},{},{},{},{},{}\n",
+            s.day, s.part, s.mean_ns, s.median_ns, s.p95_ns, s.min_ns
+        ));
+    }
+
+    out
+}
+
+fn from_csv(raw: &str) -> Result<Vec<Summary>, QuickBenchError> {
+    let mut lines = raw
+        .lines()
+        .enumerate()
+        .filter(|(_, l)| !l.trim().is_empty());
+
+    match lines.next() {
+        Some((_, header)) if header.trim() == CSV_HEADER => {}
+        _ => {
+            return Err(QuickBenchError::Csv {
+                line: 1,
+                message: format!("expected header {:?}", CSV_HEADER),
+            })
+        }
+    }
+
+    lines
+        .map(|(i, line)| {
+            let err = |message: String| QuickBenchError::Csv {
+                line: i + 1,
+                message,
+            };
+
+            let fields = line
+                .split(',')
+                .map(|f| f.trim().parse::<u64>())
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|e| err(e.to_string()))?;
+
+            match fields[..] {
+                [day, part, mean_ns, median_ns, p95_ns, min_ns] => Ok(Summary {
+                    day: day as usize,
+                    part: part as usize,
+                    mean_ns,
+                    median_ns,
+                    p95_ns,
+                    min_ns,
+                }),
+                _ => Err(err(format!("expected 6 fields, found {}", fields.len()))),
+            }
+        })
+        .collect()
+}
+
+/// Solves the given part of `problem` `iterations` times, returning a summary
+/// of the time taken by the part itself.
+pub fn measure(
+    problem: &dyn DynProblem,
+    input: &str,
+    part: Part,
+    iterations: usize,
+) -> Result<Summary, QuickBenchError> {
+    let day = problem.day();
+    let mut samples = Vec::with_capacity(iterations);
+
+    for _ in 0..iterations.max(1) {
+        let solution = problem
+            .solve_part(input, part)
+            .map_err(|source| QuickBenchError::Solve { day, part, source })?;
+
+        let elapsed = solution
+            .timing
+            .and_then(|t| match part {
+                Part::One => t.part_one,
+                Part::Two => t.part_two,
+            })
+            .ok_or(QuickBenchError::MissingTiming { day, part })?;
+
+        samples.push(elapsed.as_nanos().try_into().unwrap_or(u64::MAX));
+    }
+
+    Summary::from_samples(day, part.number(), samples)
+        .ok_or(QuickBenchError::MissingTiming { day, part })
+}
+
+/// The change in median time for one part relative to a baseline.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct Comparison {
+    pub day: usize,
+    pub part: usize,
+    pub baseline_ns: u64,
+    pub current_ns: u64,
+    /// The change as a percentage of the baseline; positive is slower.
+    pub change_pct: f64,
+    pub regressed: bool,
+}
+
+impl Display for Comparison {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{:03} part {}: {}ns -> {}ns ({:+.1}%){}",
+            self.day,
+            self.part,
+            self.baseline_ns,
+            self.current_ns,
+            self.change_pct,
+            if self.regressed { " REGRESSED" } else { "" }
+        )
+    }
+}
+
+/// Compares the median times in `current` against those in `baseline`,
+/// flagging any part that is more than `threshold_pct` percent slower.
+///
+/// Parts that are missing from the baseline are skipped.
+///
+/// ```
+/// use aoc_benchmarking::quick::{compare, Summary};
+///
+/// let baseline = [Summary::from_samples(1, 1, vec![100]).unwrap()];
+/// let current = [Summary::from_samples(1, 1, vec![120]).unwrap()];
+///
+/// let c = compare(&current, &baseline, 10.0);
+/// assert_eq!(c[0].change_pct, 20.0);
+/// assert!(c[0].regressed);
+///
+/// assert!(!compare(&current, &baseline, 25.0)[0].regressed);
+/// ```
+pub fn compare(current: &[Summary], baseline: &[Summary], threshold_pct: f64) -> Vec<Comparison> {
+    current
+        .iter()
+        .filter_map(|c| {
+            let b = baseline
+                .iter()
+                .find(|b| b.day == c.day && b.part == c.part)?;
+
+            let change_pct = if b.median_ns == 0 {
+                0.0
+            } else {
+                (c.median_ns as f64 - b.median_ns as f64) / b.median_ns as f64 * 100.0
+            };
+
+            Some(Comparison {
+                day: c.day,
+                part: c.part,
+                baseline_ns: b.median_ns,
+                current_ns: c.median_ns,
+                change_pct,
+                regressed: change_pct > threshold_pct,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn csv_round_trip() {
+        let summaries = vec![
+            Summary::from_samples(1, 1, vec![5, 3, 4]).unwrap(),
+            Summary::from_samples(17, 2, (1..=100).collect()).unwrap(),
+        ];
+
+        let raw = Format::Csv.write(&summaries).unwrap();
+        assert!(raw.starts_with(CSV_HEADER));
+        assert_eq!(Format::Csv.read(&raw).unwrap(), summaries);
+        assert_eq!(summaries[1].p95_ns, 95);
+    }
+
+    #[test]
+    fn json_round_trip() {
+        let summaries = vec![Summary::from_samples(2, 1, vec![7, 9]).unwrap()];
+
+        let raw = Format::Json.write(&summaries).unwrap();
+        assert_eq!(Format::Json.read(&raw).unwrap(), summaries);
+    }
+
+    #[test]
+    fn invalid_csv() {
+        assert!(Format::Csv.read("day,part\n1,1").is_err());
+        assert!(Format::Csv
+            .read(&format!("{}\n1,1,2,3", CSV_HEADER))
+            .is_err());
+    }
+}
@@ -0,0 +1,6 @@
+//! Links every `day-*` path dependency in this crate's manifest, see
+//! `aoc_plumbing::workspace::link_days`.
+
+fn main() {
+    aoc_plumbing::workspace::link_days();
+}
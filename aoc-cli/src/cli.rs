@@ -1,177 +1,491 @@
 use std::{
-    marker::PhantomData,
+    io::{IsTerminal, Read},
     path::{Path, PathBuf},
+    str::FromStr,
 };
 
-use anyhow::{Context, Result};
-use aoc_plumbing::Problem;
-use bridge_repair::BridgeRepair;
-use ceres_search::CeresSearch;
-use chronospatial_computer::ChronospatialComputer;
-use clap::{Args, CommandFactory, Parser, Subcommand};
+use anyhow::{anyhow, bail, Context, Result};
+use aoc_plumbing::{
+    answers::Answers,
+    registry::{self, DynProblem},
+    ParseError, Part,
+};
+use chronospatial_computer::{debugger::Debugger, ChronospatialComputer};
+use clap::{ArgMatches, Args, Command, CommandFactory, FromArgMatches, Parser, Subcommand};
 use clap_complete::{generate, shells::Zsh};
-use claw_contraption::ClawContraption;
-use disk_fragmenter::DiskFragmenter;
-use garden_groups::GardenGroups;
-use guard_gallivant::GuardGallivant;
-use historian_hysteria::HistorianHysteria;
-use hoof_it::HoofIt;
-use linen_layout::LinenLayout;
-use mull_it_over::MullItOver;
-use plutonian_pebbles::PlutonianPebbles;
-use print_queue::PrintQueue;
-use race_condition::RaceCondition;
-use ram_run::RamRun;
-use red_nosed_reports::RedNosedReports;
-use reindeer_maze::ReindeerMaze;
-use resonant_collinearity::ResonantCollinearity;
-use restroom_redoubt::RestroomRedoubt;
-use warehouse_woes::WarehouseWoes;
-// import_marker
-
-// I'm not proud
-macro_rules! generate_cli {
-    ($(($name:ident, $day:literal)),* $(,)?) => {
-        /// Advent of Code solutions for 2024
-        #[derive(Parser)]
-        #[command(name = "aoc", author, version)]
-        #[command(help_template = "\
-            {name} {version} by {author}
-            {about-section}\n{usage-heading} {usage}\n\n{all-args}{tab}")]
-        pub(crate) struct Cli {
-            #[command(subcommand)]
-            pub command: Commands,
-        }
 
-        impl Cli {
-            pub fn run() -> Result<()> {
-                let command = Self::parse().command;
-                command.run()
-            }
+use crate::report::{self, Answer, DayReport, InputReport, VerifyReport, STDIN_PATH};
+
+/// Advent of Code solutions for 2024
+#[derive(Parser)]
+#[command(name = "aoc", author, version)]
+#[command(help_template = "\
+    {name} {version} by {author}
+    {about-section}\n{usage-heading} {usage}\n\n{all-args}{tab}")]
+pub(crate) struct Cli {
+    #[command(subcommand)]
+    pub command: Commands,
+}
+
+impl Cli {
+    pub fn run() -> Result<()> {
+        let command = Self::parse().command;
+        command.run()
+    }
+}
+
+#[derive(Subcommand)]
+pub(crate) enum Commands {
+    #[command(flatten)]
+    Day(DayCommand),
+
+    #[command(display_order = 30)]
+    Run(Run),
+
+    #[command(display_order = 31)]
+    RunAll(RunAll),
+
+    #[command(display_order = 32)]
+    Verify(Verify),
+
+    #[command(display_order = 33)]
+    Debug(DebugCommand),
+
+    #[command(display_order = 34)]
+    GenerateCompletions(GenerateCompletions),
+}
+
+impl Commands {
+    pub fn run(&self) -> Result<()> {
+        match self {
+            Self::GenerateCompletions(cmd) => cmd.run(),
+            Self::Run(cmd) => cmd.run(),
+            Self::RunAll(cmd) => cmd.run(),
+            Self::Verify(cmd) => cmd.run(),
+            Self::Debug(cmd) => cmd.run(),
+            Self::Day(cmd) => cmd.run(),
         }
+    }
+}
+
+/// A subcommand for each registered day, named after the day's hyphenated
+/// title.
+///
+/// These are generated at runtime from the problem registry, so clap's derive
+/// can't be used here.
+pub(crate) struct DayCommand {
+    problem: &'static dyn DynProblem,
+    solver: Solver,
+}
+
+impl DayCommand {
+    pub fn run(&self) -> Result<()> {
+        self.solver.run(self.problem)
+    }
+}
+
+impl FromArgMatches for DayCommand {
+    fn from_arg_matches(matches: &ArgMatches) -> Result<Self, clap::Error> {
+        let (name, sub_matches) = matches
+            .subcommand()
+            .ok_or_else(|| clap::Error::new(clap::error::ErrorKind::MissingSubcommand))?;
 
-        #[derive(Subcommand)]
-        pub(crate) enum Commands {
-            $(
-            #[command(about = $name::problem_label(), long_about = $name::long_description(), display_order = $day)]
-            $name(Solver<$name>),
-            )*
+        let problem = registry::problems()
+            .into_iter()
+            .find(|p| p.name() == name)
+            .ok_or_else(|| clap::Error::new(clap::error::ErrorKind::InvalidSubcommand))?;
+
+        Ok(Self {
+            problem,
+            solver: Solver::from_arg_matches(sub_matches)?,
+        })
+    }
 
-            #[command(display_order = 30)]
-            Run(Run),
+    fn update_from_arg_matches(&mut self, matches: &ArgMatches) -> Result<(), clap::Error> {
+        *self = Self::from_arg_matches(matches)?;
+        Ok(())
+    }
+}
 
-            #[command(display_order = 31)]
-            GenerateCompletions(GenerateCompletions),
+impl Subcommand for DayCommand {
+    fn augment_subcommands(mut cmd: Command) -> Command {
+        for problem in registry::problems() {
+            cmd = cmd.subcommand(Solver::augment_args(
+                Command::new(problem.name())
+                    .about(problem.problem_label())
+                    .long_about(problem.long_description())
+                    .display_order(problem.day()),
+            ));
         }
+        cmd
+    }
 
-        impl Commands {
-            pub fn run(&self) -> Result<()> {
-                match self {
-                    Self::GenerateCompletions(cmd) => cmd.run(),
-                    Self::Run(cmd) => cmd.run(),
-                    $(
-                    Self::$name(cmd) => cmd.run(),
-                    )*
+    fn augment_subcommands_for_update(cmd: Command) -> Command {
+        Self::augment_subcommands(cmd)
+    }
+
+    fn has_subcommand(name: &str) -> bool {
+        registry::problems().iter().any(|p| p.name() == name)
+    }
+}
+
+/// Run the solution for a specified day with a specified input.
+///
+/// The day must be implemented and the specified input must exist.
+#[derive(Args)]
+pub(crate) struct Run {
+    /// The day to run.
+    ///
+    /// This may be specified instead by setting the `AOC_DAY` env var.
+    /// An explicitly passed value will take precendence over the env
+    /// var.
+    #[clap(env = "AOC_DAY")]
+    day: usize,
+
+    /// The path(s) to the input for this solution.
+    ///
+    /// Use `-` to read the input from stdin. If more than one input is
+    /// given, each is solved in turn and labelled in the output.
+    ///
+    /// This may be specified instead by setting the `AOC_INPUT` env
+    /// var. An explicitly passed value will take precendence over the
+    /// env var.
+    #[clap(env = "AOC_INPUT", num_args = 1.., required = true)]
+    input: Vec<PathBuf>,
+
+    /// Display the output as json.
+    ///
+    /// This may be specified instead by setting the `AOC_INPUT` env
+    /// var to `true`. If the flag is passed, on the command line, it
+    /// will take precendence over the env var.
+    #[clap(short, long, env = "AOC_JSON")]
+    json: bool,
+
+    /// Report the time taken to parse and to solve each part.
+    ///
+    /// This may be specified instead by setting the `AOC_TIME` env var
+    /// to `true`. If the flag is passed, on the command line, it will
+    /// take precendence over the env var.
+    #[clap(short, long, env = "AOC_TIME")]
+    time: bool,
+
+    /// Only solve the given part (1 or 2).
+    ///
+    /// This may be specified instead by setting the `AOC_PART` env var.
+    /// An explicitly passed value will take precendence over the env
+    /// var.
+    #[clap(short, long, env = "AOC_PART")]
+    part: Option<Part>,
+}
+
+impl Run {
+    pub fn run(&self) -> Result<()> {
+        match registry::find(self.day) {
+            Some(problem) => _run(problem, &self.input, self.json, self.time, self.part),
+            None => {
+                if self.json {
+                    println!("\"not implemented\"");
+                } else {
+                    println!("not implemented");
                 }
+                Ok(())
             }
         }
+    }
+}
+
+/// Run the solutions for every implemented day.
+///
+/// Each day's input is expected at `<ROOT>/day-NNN-*/input.txt`. A
+/// failing day does not stop the remaining days from running, but
+/// will cause a non-zero exit once every day has been attempted.
+#[derive(Args)]
+pub(crate) struct RunAll {
+    /// The directory containing the `day-NNN-*` directories.
+    ///
+    /// This may be specified instead by setting the `AOC_ROOT` env
+    /// var. An explicitly passed value will take precendence over the
+    /// env var.
+    #[clap(long, env = "AOC_ROOT", default_value = ".")]
+    root: PathBuf,
+
+    /// Display the output as a json array.
+    ///
+    /// This may be specified instead by setting the `AOC_JSON` ENV var
+    /// to `true`. If the flag is passed, on the command line, it will
+    /// take precendence over the env var.
+    #[clap(short, long, env = "AOC_JSON")]
+    json: bool,
+}
+
+impl RunAll {
+    pub fn run(&self) -> Result<()> {
+        let reports = registry::problems()
+            .into_iter()
+            .map(|p| DayReport::generate(p, &self.root))
+            .collect::<Vec<_>>();
 
-        /// Run the solution for a specified day with a specified input.
-        ///
-        /// The day must be implemented and the specified input must exist.
-        #[derive(Args)]
-        pub(crate) struct Run {
-            /// The day to run.
-            ///
-            /// This may be specified instead by setting the `AOC_DAY` env var.
-            /// An explicitly passed value will take precendence over the env
-            /// var.
-            #[clap(env = "AOC_DAY")]
-            day: usize,
-
-            /// The path to the input for this solution.
-            ///
-            /// This may be specified instead by setting the `AOC_INPUT` env
-            /// var. An explicitly passed value will take precendence over the
-            /// env var.
-            #[clap(env = "AOC_INPUT")]
-            input: PathBuf,
-
-            /// Display the output as json.
-            ///
-            /// This may be specified instead by setting the `AOC_INPUT` env
-            /// var to `true`. If the flag is passed, on the command line, it
-            /// will take precendence over the env var.
-            #[clap(short, long, env = "AOC_JSON")]
-            json: bool,
+        if self.json {
+            println!("{}", serde_json::to_string(&reports)?);
+        } else {
+            report::print_table(&reports);
         }
 
-        impl Run {
-            pub fn run(&self) -> Result<()> {
-                match self.day {
-                    $(
-                    $day => _run::<$name>(&self.input, self.json),
-                    )*
-                    _ => {
-                        if self.json {
-                            println!("\"not implemented\"");
-                        } else {
-                            println!("not implemented");
-                        }
-                        Ok(())
-                    }
-                }
-            }
+        let failed = reports.iter().filter(|r| r.is_failure()).count();
+        if failed > 0 {
+            bail!("{} of {} days failed", failed, reports.len());
         }
-    };
+
+        Ok(())
+    }
 }
 
+/// Check the solutions for every implemented day against a file of
+/// known answers.
+///
+/// Each part is reported as passing, failing (with the expected and
+/// actual values), or missing if no answer is stored for it. Any
+/// failure, including a day that could not be solved, will cause a
+/// non-zero exit.
 #[derive(Args)]
-pub(crate) struct Solver<T>
-where
-    T: Problem,
-{
-    /// The path to the input for this solution.
+pub(crate) struct Verify {
+    /// The answers file to check against.
+    ///
+    /// Defaults to `answers.toml` in the root directory.
+    #[clap(long, env = "AOC_ANSWERS")]
+    answers: Option<PathBuf>,
+
+    /// The directory containing the `day-NNN-*` directories.
+    ///
+    /// This may be specified instead by setting the `AOC_ROOT` env
+    /// var. An explicitly passed value will take precendence over the
+    /// env var.
+    #[clap(long, env = "AOC_ROOT", default_value = ".")]
+    root: PathBuf,
+
+    /// Display the output as a json array.
+    ///
+    /// This may be specified instead by setting the `AOC_JSON` ENV var
+    /// to `true`. If the flag is passed, on the command line, it will
+    /// take precendence over the env var.
+    #[clap(short, long, env = "AOC_JSON")]
+    json: bool,
+}
+
+impl Verify {
+    pub fn run(&self) -> Result<()> {
+        let answers_path = self
+            .answers
+            .clone()
+            .unwrap_or_else(|| self.root.join("answers.toml"));
+        let answers = Answers::load(&answers_path)
+            .with_context(|| format!("Could not load answers from {}", answers_path.display()))?;
+
+        let reports = registry::problems()
+            .into_iter()
+            .map(|p| VerifyReport::new(DayReport::generate(p, &self.root), &answers))
+            .collect::<Vec<_>>();
+
+        if self.json {
+            println!("{}", serde_json::to_string(&reports)?);
+        } else {
+            report::print_verification(&reports);
+        }
+
+        let failed = reports.iter().filter(|r| r.is_failure()).count();
+        if failed > 0 {
+            bail!("{} of {} days failed verification", failed, reports.len());
+        }
+
+        Ok(())
+    }
+}
+
+/// Step through a day 17 (chronospatial computer) program interactively.
+///
+/// The program, registers and output are redrawn after each command,
+/// which are read from stdin one per line: `s [n]` to step, `c` to
+/// continue, `b <ip>|out` and `d [<ip>|out]` to add and delete
+/// breakpoints, `set <reg> <value>` to change a register, `r [a]` to
+/// restart (optionally with a new value for register A) and `q` to quit.
+#[derive(Args)]
+pub(crate) struct DebugCommand {
+    /// The path to the input containing the program.
     input: PathBuf,
 
+    /// Break before the instruction at the given index. May be repeated.
+    #[clap(short, long = "break")]
+    breakpoints: Vec<usize>,
+
+    /// Break whenever the program outputs a value.
+    #[clap(short = 'o', long)]
+    break_on_output: bool,
+
+    /// Start with this value in register A instead of the input's.
+    #[clap(short = 'a', long)]
+    register_a: Option<u64>,
+
+    /// Print the disassembled program and what each output depends on, then
+    /// exit without debugging.
+    #[clap(short, long)]
+    disassemble: bool,
+}
+
+impl DebugCommand {
+    pub fn run(&self) -> Result<()> {
+        if self.input == Path::new(STDIN_PATH) && !self.disassemble {
+            bail!("The debugger reads its commands from stdin, so the input must be a file");
+        }
+
+        let input = read_input(&self.input, &mut None)?;
+        let computer = ChronospatialComputer::from_str(&input)
+            .map_err(|e| anyhow!("Failed to parse input\n\n{}", e.render(&input)))?;
+
+        if self.disassemble {
+            print!("{}", computer.disassemble());
+            match computer.analyse() {
+                Ok(analysis) => println!("\n{}", analysis),
+                Err(e) => println!("\ncannot analyse the program: {}", e),
+            }
+            return Ok(());
+        }
+
+        let mut debugger = Debugger::new(&computer);
+        for ip in self.breakpoints.iter() {
+            debugger.add_breakpoint(*ip);
+        }
+        debugger.break_on_output(self.break_on_output);
+        if self.register_a.is_some() {
+            debugger.restart(self.register_a);
+        }
+
+        let stdout = std::io::stdout();
+        let clear = stdout.is_terminal();
+        debugger
+            .run(std::io::stdin().lock(), stdout.lock(), clear)
+            .context("Debugger failed")
+    }
+}
+
+#[derive(Args)]
+pub(crate) struct Solver {
+    /// The path(s) to the input for this solution.
+    ///
+    /// Use `-` to read the input from stdin. If more than one input is
+    /// given, each is solved in turn and labelled in the output.
+    #[clap(num_args = 1.., required = true)]
+    input: Vec<PathBuf>,
+
     /// Display the output as json.
     #[clap(short, long)]
     json: bool,
 
-    #[clap(skip)]
-    _phantom: PhantomData<T>,
+    /// Report the time taken to parse and to solve each part.
+    #[clap(short, long)]
+    time: bool,
+
+    /// Only solve the given part (1 or 2).
+    #[clap(short, long)]
+    part: Option<Part>,
 }
 
-impl<T> Solver<T>
-where
-    T: Problem,
-    <T as Problem>::ProblemError: Into<anyhow::Error>,
-{
-    pub fn run(&self) -> Result<()> {
-        _run::<T>(&self.input, self.json)
+impl Solver {
+    pub fn run(&self, problem: &dyn DynProblem) -> Result<()> {
+        _run(problem, &self.input, self.json, self.time, self.part)
     }
 }
 
-fn _run<T>(input_file: &Path, json: bool) -> Result<()>
-where
-    T: Problem,
-    <T as Problem>::ProblemError: Into<anyhow::Error>,
-{
-    let input = std::fs::read_to_string(input_file).context("Could not read input file")?;
+fn _run(
+    problem: &dyn DynProblem,
+    inputs: &[PathBuf],
+    json: bool,
+    time: bool,
+    part: Option<Part>,
+) -> Result<()> {
+    let mut stdin = None;
+
+    if let [input_file] = inputs {
+        let input = read_input(input_file, &mut stdin)?;
+        let solution = solve(problem, &input, time, part)?;
+
+        if json {
+            println!("{}", serde_json::to_string(&solution)?);
+        } else {
+            println!("{}", solution);
+        }
 
-    let solution = T::solve(&input)
-        .map_err(Into::<anyhow::Error>::into)
-        .context("Failed to solve")?;
+        return Ok(());
+    }
+
+    let reports = inputs
+        .iter()
+        .map(|input_file| {
+            let result = read_input(input_file, &mut stdin)
+                .and_then(|input| solve(problem, &input, time, part));
+            InputReport::new(input_file, result)
+        })
+        .collect::<Vec<_>>();
 
     if json {
-        println!("{}", serde_json::to_string(&solution)?);
+        println!("{}", serde_json::to_string(&reports)?);
     } else {
-        println!("{}", solution);
+        report::print_inputs(&reports);
+    }
+
+    let failed = reports.iter().filter(|r| r.is_failure()).count();
+    if failed > 0 {
+        bail!("{} of {} inputs failed", failed, reports.len());
     }
 
     Ok(())
 }
 
+fn solve(problem: &dyn DynProblem, input: &str, time: bool, part: Option<Part>) -> Result<Answer> {
+    let answer = match part {
+        Some(part) => problem.solve_part(input, part).map(|mut s| {
+            if !time {
+                s.timing = None;
+            }
+            Answer::Single(s)
+        }),
+        None if time => problem.solve_timed(input).map(Answer::Both),
+        None => problem.solve(input).map(Answer::Both),
+    };
+
+    // the rendered parse error already includes its message, so it replaces
+    // the original error rather than adding context to it
+    answer.map_err(|e| match find_parse_error(&*e) {
+        Some(pe) => anyhow!("Failed to parse input\n\n{}", pe.render(input)),
+        None => anyhow!(e).context("Failed to solve"),
+    })
+}
+
+/// Searches the chain of sources for a [`ParseError`], so that its location
+/// in the input can be shown.
+fn find_parse_error<'a>(e: &'a (dyn std::error::Error + 'static)) -> Option<&'a ParseError> {
+    std::iter::successors(Some(e), |e| e.source()).find_map(|e| e.downcast_ref::<ParseError>())
+}
+
+/// Reads the input at the given path, or from stdin if the path is `-`. As
+/// stdin can only be consumed once, its contents are cached in `stdin` so that
+/// repeated uses of `-` see the same input.
+fn read_input(path: &Path, stdin: &mut Option<String>) -> Result<String> {
+    if path != Path::new(STDIN_PATH) {
+        return std::fs::read_to_string(path)
+            .with_context(|| format!("Could not read input file {}", path.display()));
+    }
+
+    if stdin.is_none() {
+        let mut buf = String::new();
+        std::io::stdin()
+            .read_to_string(&mut buf)
+            .context("Could not read input from stdin")?;
+        *stdin = Some(buf);
+    }
+
+    Ok(stdin.clone().unwrap_or_default())
+}
+
 /// Generate zsh completions
 #[derive(Debug, Args)]
 pub struct GenerateCompletions;
@@ -182,27 +496,3 @@ impl GenerateCompletions {
         Ok(())
     }
 }
-
-generate_cli! {
-    (HistorianHysteria, 1),
-    (RedNosedReports, 2),
-    (MullItOver, 3),
-    (CeresSearch, 4),
-    (PrintQueue, 5),
-    (GuardGallivant, 6),
-    (BridgeRepair, 7),
-    (ResonantCollinearity, 8),
-    (DiskFragmenter, 9),
-    (HoofIt, 10),
-    (PlutonianPebbles, 11),
-    (GardenGroups, 12),
-    (ClawContraption, 13),
-    (RestroomRedoubt, 14),
-    (WarehouseWoes, 15),
-    (ReindeerMaze, 16),
-    (ChronospatialComputer, 17),
-    (RamRun, 18),
-    (LinenLayout, 19),
-    (RaceCondition, 20),
-    // command_marker
-}
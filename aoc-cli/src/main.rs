@@ -1,4 +1,8 @@
+// link every day's crate so that its problem is present in the registry
+include!(concat!(env!("OUT_DIR"), "/days.rs"));
+
 mod cli;
+mod report;
 
 pub fn main() -> Result<(), anyhow::Error> {
     cli::Cli::run()
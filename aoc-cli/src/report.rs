@@ -0,0 +1,274 @@
+use std::{
+    any::Any,
+    fmt::Display,
+    panic::{self, AssertUnwindSafe},
+    path::{Path, PathBuf},
+};
+
+use anyhow::{anyhow, Context, Result};
+use aoc_plumbing::{
+    answers::{Answers, PartCheck},
+    registry::DynProblem,
+    workspace, PartSolution, Solution,
+};
+use serde::Serialize;
+
+/// The outcome of solving a single day as part of a batch run.
+#[derive(Debug, Serialize)]
+pub(crate) struct DayReport {
+    pub day: usize,
+    pub title: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub solution: Option<Solution<String, String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl DayReport {
+    /// Locates the input for the given problem under `root` and solves it,
+    /// recording either the (timed) solution or the reason it failed. Panics
+    /// inside the solution are caught and reported as failures.
+    pub fn generate(problem: &dyn DynProblem, root: &Path) -> Self {
+        let result = find_input(root, problem.day()).and_then(|path| {
+            let input = std::fs::read_to_string(&path)
+                .with_context(|| format!("Could not read input file {}", path.display()))?;
+
+            match panic::catch_unwind(AssertUnwindSafe(|| problem.solve_timed(&input))) {
+                Ok(res) => res.map_err(|e| anyhow!(e)).context("Failed to solve"),
+                Err(payload) => Err(anyhow!("panicked: {}", panic_message(&*payload))),
+            }
+        });
+
+        match result {
+            Ok(solution) => Self {
+                day: problem.day(),
+                title: problem.title(),
+                solution: Some(solution),
+                error: None,
+            },
+            Err(e) => Self {
+                day: problem.day(),
+                title: problem.title(),
+                solution: None,
+                error: Some(format!("{:#}", e)),
+            },
+        }
+    }
+
+    pub fn is_failure(&self) -> bool {
+        self.error.is_some()
+    }
+}
+
+/// The path used on the command line to denote stdin.
+pub(crate) const STDIN_PATH: &str = "-";
+
+/// The answer(s) for either both parts of a problem, or just one of them.
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
+pub(crate) enum Answer {
+    Both(Solution<String, String>),
+    Single(PartSolution<String>),
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Both(s) => s.fmt(f),
+            Self::Single(s) => s.fmt(f),
+        }
+    }
+}
+
+/// The outcome of solving one of several inputs for a single day.
+#[derive(Debug, Serialize)]
+pub(crate) struct InputReport {
+    pub input: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub solution: Option<Answer>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl InputReport {
+    pub fn new(path: &Path, result: Result<Answer>) -> Self {
+        let input = if path == Path::new(STDIN_PATH) {
+            "<stdin>".to_string()
+        } else {
+            path.display().to_string()
+        };
+
+        match result {
+            Ok(solution) => Self {
+                input,
+                solution: Some(solution),
+                error: None,
+            },
+            Err(e) => Self {
+                input,
+                solution: None,
+                error: Some(format!("{:#}", e)),
+            },
+        }
+    }
+
+    pub fn is_failure(&self) -> bool {
+        self.error.is_some()
+    }
+}
+
+/// The outcome of checking a single day against the stored answers.
+#[derive(Debug, Serialize)]
+pub(crate) struct VerifyReport {
+    pub day: usize,
+    pub title: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub part_one: Option<PartCheck>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub part_two: Option<PartCheck>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl VerifyReport {
+    pub fn new(report: DayReport, answers: &Answers) -> Self {
+        let (part_one, part_two) = match report.solution {
+            Some(ref s) => {
+                let [a, b] = answers.check(report.day, s);
+                (Some(a), Some(b))
+            }
+            None => (None, None),
+        };
+
+        Self {
+            day: report.day,
+            title: report.title,
+            part_one,
+            part_two,
+            error: report.error,
+        }
+    }
+
+    pub fn is_failure(&self) -> bool {
+        self.error.is_some() || self.checks().any(PartCheck::is_failure)
+    }
+
+    fn checks(&self) -> impl Iterator<Item = &PartCheck> {
+        self.part_one.iter().chain(self.part_two.iter())
+    }
+}
+
+/// Finds `<root>/day-NNN-*/input.txt` for the given day.
+pub(crate) fn find_input(root: &Path, day: usize) -> Result<PathBuf> {
+    workspace::find_input(root, day)
+        .with_context(|| format!("Could not read directory {}", root.display()))?
+        .ok_or_else(|| {
+            anyhow!(
+                "Could not find day-{:03}-*/input.txt in {}",
+                day,
+                root.display()
+            )
+        })
+}
+
+/// Prints the reports as a plaintext table.
+pub(crate) fn print_table(reports: &[DayReport]) {
+    let header = ["day", "title", "part 1", "part 2", "time"];
+    let rows: Vec<[String; 5]> = reports
+        .iter()
+        .map(|r| match (&r.solution, &r.error) {
+            (Some(s), _) => [
+                format!("{:03}", r.day),
+                r.title.to_string(),
+                s.part_one.clone(),
+                s.part_two.clone(),
+                s.timing
+                    .map(|t| format!("{:?}", t.total()))
+                    .unwrap_or_default(),
+            ],
+            (None, e) => [
+                format!("{:03}", r.day),
+                r.title.to_string(),
+                format!("error: {}", e.as_deref().unwrap_or("unknown")),
+                String::new(),
+                String::new(),
+            ],
+        })
+        .collect();
+
+    let mut widths = header.map(str::len);
+    for row in rows.iter() {
+        for (w, cell) in widths.iter_mut().zip(row.iter()) {
+            *w = (*w).max(cell.chars().count());
+        }
+    }
+
+    let print_row = |cells: &[&str]| {
+        let line = cells
+            .iter()
+            .zip(widths.iter())
+            .map(|(c, w)| format!("{:<w$}", c, w = *w))
+            .collect::<Vec<_>>()
+            .join("  ");
+        println!("{}", line.trim_end());
+    };
+
+    print_row(&header);
+    for row in rows.iter() {
+        print_row(&row.each_ref().map(String::as_str));
+    }
+}
+
+/// Prints the per-part verification results followed by a summary line.
+pub(crate) fn print_verification(reports: &[VerifyReport]) {
+    let (mut passed, mut failed, mut missing) = (0, 0, 0);
+
+    for r in reports.iter() {
+        println!("{:03} {}", r.day, r.title);
+
+        if let Some(ref e) = r.error {
+            println!("  error: {}", e);
+            failed += 1;
+            continue;
+        }
+
+        for (i, check) in r.checks().enumerate() {
+            println!("  part {}: {}", i + 1, check);
+            match check {
+                PartCheck::Pass => passed += 1,
+                PartCheck::Fail { .. } => failed += 1,
+                PartCheck::Missing => missing += 1,
+            }
+        }
+    }
+
+    println!(
+        "\n{} passed, {} failed, {} missing",
+        passed, failed, missing
+    );
+}
+
+/// Prints each input's solution (or error) under a header naming the input.
+pub(crate) fn print_inputs(reports: &[InputReport]) {
+    for (i, r) in reports.iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+
+        println!("==> {} <==", r.input);
+        match (&r.solution, &r.error) {
+            (Some(s), _) => println!("{}", s),
+            (None, e) => println!("error: {}", e.as_deref().unwrap_or("unknown")),
+        }
+    }
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> &str {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        s
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s
+    } else {
+        "unknown panic"
+    }
+}
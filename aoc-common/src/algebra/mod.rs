@@ -5,6 +5,7 @@ mod vector;
 pub use point::Point2;
 pub use point::Point3;
 pub use ray::Ray;
+pub use vector::Vector2;
 pub use vector::Vector3;
 
 pub const EPSILON: f64 = 1e-6;
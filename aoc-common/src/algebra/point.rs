@@ -1,8 +1,9 @@
 use std::ops::{Add, Div, Mul, Neg, Sub};
 
-use num::Num;
+use num::{traits::Euclid, Num, Signed};
 
-use super::Vector3;
+use super::{Vector2, Vector3};
+use crate::grid::Coordinate;
 
 #[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Point2<T>
@@ -17,11 +18,42 @@ impl<T> Point2<T>
 where
     T: Copy + Num,
 {
+    pub fn origin() -> Self {
+        Self {
+            x: T::zero(),
+            y: T::zero(),
+        }
+    }
+
     pub fn new(x: T, y: T) -> Self {
         Self { x, y }
     }
 }
 
+impl<T> Point2<T>
+where
+    T: Copy + Num + Euclid,
+{
+    /// Wraps the point into the rectangle from the origin to `bounds`, as if
+    /// space were a torus.
+    pub fn rem_euclid(&self, bounds: &Vector2<T>) -> Self {
+        Self::new(self.x.rem_euclid(&bounds.x), self.y.rem_euclid(&bounds.y))
+    }
+}
+
+impl<T> Point2<T>
+where
+    T: Copy + Num + Signed + PartialOrd,
+{
+    pub fn manhattan_distance(&self, other: &Self) -> T {
+        (*other - *self).manhattan()
+    }
+
+    pub fn chebyshev_distance(&self, other: &Self) -> T {
+        (*other - *self).chebyshev()
+    }
+}
+
 impl<T> From<[T; 2]> for Point2<T>
 where
     T: Copy + Num,
@@ -49,6 +81,74 @@ where
     }
 }
 
+/// A coordinate's column is x and its row is y, so y points down.
+impl From<Coordinate> for Point2<isize> {
+    fn from(c: Coordinate) -> Self {
+        Self::new(c.1, c.0)
+    }
+}
+
+impl From<Point2<isize>> for Coordinate {
+    fn from(p: Point2<isize>) -> Self {
+        Self(p.y, p.x)
+    }
+}
+
+impl<T> Add<Vector2<T>> for Point2<T>
+where
+    T: Copy + Num + Add<Output = T>,
+{
+    type Output = Self;
+
+    fn add(self, rhs: Vector2<T>) -> Self::Output {
+        Self::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl<T> Sub for Point2<T>
+where
+    T: Copy + Num + Sub,
+{
+    type Output = Vector2<T>;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Vector2::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl<T> Sub<Vector2<T>> for Point2<T>
+where
+    T: Copy + Num,
+{
+    type Output = Point2<T>;
+
+    fn sub(self, rhs: Vector2<T>) -> Self::Output {
+        Self::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl<T> Neg for Point2<T>
+where
+    T: Copy + Num + Neg<Output = T>,
+{
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self::new(-self.x, -self.y)
+    }
+}
+
+impl<T> Mul<T> for Point2<T>
+where
+    T: Copy + Num + Mul<Output = T>,
+{
+    type Output = Self;
+
+    fn mul(self, rhs: T) -> Self::Output {
+        Self::new(self.x * rhs, self.y * rhs)
+    }
+}
+
 impl<T> Div<T> for Point2<T>
 where
     T: Copy + Num + Div<Output = T>,
@@ -146,6 +246,42 @@ where
 mod tests {
     use super::*;
 
+    #[test]
+    fn point2_arithmetic() {
+        let p = Point2::new(1, 2);
+
+        assert_eq!(p + Vector2::new(3, -4), Point2::new(4, -2));
+        assert_eq!(Point2::new(5, 5) - p, Vector2::new(4, 3));
+        assert_eq!(p - Vector2::new(1, 1), Point2::new(0, 1));
+        assert_eq!(-p, Point2::new(-1, -2));
+        assert_eq!(p * 3, Point2::new(3, 6));
+        assert_eq!(Point2::new(4, 6) / 2, Point2::new(2, 3));
+    }
+
+    #[test]
+    fn point2_distances() {
+        let a = Point2::new(-1, 3);
+        let b = Point2::new(4, 1);
+        assert_eq!(a.manhattan_distance(&b), 7);
+        assert_eq!(a.chebyshev_distance(&b), 5);
+        assert_eq!(a.manhattan_distance(&a), 0);
+    }
+
+    #[test]
+    fn point2_rem_euclid() {
+        let bounds = Vector2::new(11, 7);
+        assert_eq!(Point2::new(-1, 15).rem_euclid(&bounds), Point2::new(10, 1));
+        assert_eq!(Point2::new(3, 2).rem_euclid(&bounds), Point2::new(3, 2));
+    }
+
+    #[test]
+    fn point2_coordinate() {
+        let p: Point2<isize> = Coordinate(2, 5).into();
+        assert_eq!(p, Point2::new(5, 2));
+        assert_eq!(Coordinate::from(p), Coordinate(2, 5));
+        assert_eq!(Coordinate::from(Point2::origin()), Coordinate(0, 0));
+    }
+
     #[test]
     fn point_add_vector() {
         let expected = Point3::new(4.0, 3.0, 1.0);
@@ -7,7 +7,7 @@ use crate::algebra::EPSILON;
 use crate::geometry::IntersectRay;
 use crate::geometry::Intersection;
 
-#[derive(Debug, Copy, Clone, PartialEq)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub struct Ray<T>
 where
     T: Copy + Num,
@@ -1,8 +1,194 @@
 use std::ops::{Add, Div, Mul, Neg, Sub};
 
-use num::{Float, Num};
+use num::{traits::Euclid, Float, Num, Signed};
 
-#[derive(Default, Debug, Clone, Copy, PartialEq)]
+use crate::grid::Coordinate;
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Vector2<T>
+where
+    T: Copy + Num,
+{
+    pub x: T,
+    pub y: T,
+}
+
+impl<T> Vector2<T>
+where
+    T: Copy + Num,
+{
+    pub fn zero() -> Self {
+        Self {
+            x: T::zero(),
+            y: T::zero(),
+        }
+    }
+
+    pub fn i() -> Self {
+        Self {
+            x: T::one(),
+            y: T::zero(),
+        }
+    }
+
+    pub fn j() -> Self {
+        Self {
+            x: T::zero(),
+            y: T::one(),
+        }
+    }
+
+    pub fn new(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+
+    pub fn dot(&self, rhs: &Self) -> T {
+        self.x * rhs.x + self.y * rhs.y
+    }
+
+    /// The perp-dot product, which is the z component of the cross product
+    /// of the two vectors extended to 3D. It is positive if `rhs` is
+    /// anticlockwise from `self` (with y pointing up) and zero if they are
+    /// parallel.
+    pub fn cross(&self, rhs: &Self) -> T {
+        self.x * rhs.y - self.y * rhs.x
+    }
+
+    pub fn norm(&self) -> T {
+        self.dot(self)
+    }
+}
+
+impl<T> Vector2<T>
+where
+    T: Copy + Num + Euclid,
+{
+    /// Wraps each component into `0..bounds`.
+    pub fn rem_euclid(&self, bounds: &Self) -> Self {
+        Self::new(self.x.rem_euclid(&bounds.x), self.y.rem_euclid(&bounds.y))
+    }
+}
+
+impl<T> Vector2<T>
+where
+    T: Copy + Num + Signed + PartialOrd,
+{
+    /// The sum of the absolute values of the components.
+    pub fn manhattan(&self) -> T {
+        self.x.abs() + self.y.abs()
+    }
+
+    /// The largest absolute value of the components.
+    pub fn chebyshev(&self) -> T {
+        let (x, y) = (self.x.abs(), self.y.abs());
+        if x > y {
+            x
+        } else {
+            y
+        }
+    }
+}
+
+impl<T> Vector2<T>
+where
+    T: Copy + Num + Float,
+{
+    pub fn magnitude(&self) -> T {
+        self.norm().sqrt()
+    }
+
+    pub fn normalize(&self) -> Self {
+        *self / self.magnitude()
+    }
+}
+
+impl<T> From<[T; 2]> for Vector2<T>
+where
+    T: Copy + Num,
+{
+    fn from(v: [T; 2]) -> Self {
+        Self::new(v[0], v[1])
+    }
+}
+
+impl<T> From<(T, T)> for Vector2<T>
+where
+    T: Copy + Num,
+{
+    fn from(v: (T, T)) -> Self {
+        Self::new(v.0, v.1)
+    }
+}
+
+/// A coordinate's column is x and its row is y, so y points down.
+impl From<Coordinate> for Vector2<isize> {
+    fn from(c: Coordinate) -> Self {
+        Self::new(c.1, c.0)
+    }
+}
+
+impl From<Vector2<isize>> for Coordinate {
+    fn from(v: Vector2<isize>) -> Self {
+        Self(v.y, v.x)
+    }
+}
+
+impl<T> Add for Vector2<T>
+where
+    T: Copy + Num + Add<Output = T>,
+{
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl<T> Sub for Vector2<T>
+where
+    T: Copy + Num + Sub<Output = T>,
+{
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl<T> Neg for Vector2<T>
+where
+    T: Copy + Num + Neg<Output = T>,
+{
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self::new(-self.x, -self.y)
+    }
+}
+
+impl<T> Mul<T> for Vector2<T>
+where
+    T: Copy + Num + Mul<Output = T>,
+{
+    type Output = Self;
+
+    fn mul(self, rhs: T) -> Self::Output {
+        Self::new(self.x * rhs, self.y * rhs)
+    }
+}
+
+impl<T> Div<T> for Vector2<T>
+where
+    T: Copy + Num + Div<Output = T>,
+{
+    type Output = Self;
+
+    fn div(self, rhs: T) -> Self {
+        Self::new(self.x / rhs, self.y / rhs)
+    }
+}
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Vector3<T>
 where
     T: Copy + Num,
@@ -150,7 +336,44 @@ where
 
 #[cfg(test)]
 mod tests {
-    use super::Vector3;
+    use super::{Coordinate, Vector2, Vector3};
+
+    #[test]
+    fn vector2_arithmetic() {
+        let a = Vector2::new(3, -4);
+        let b = Vector2::new(1, 2);
+
+        assert_eq!(a + b, Vector2::new(4, -2));
+        assert_eq!(a - b, Vector2::new(2, -6));
+        assert_eq!(-a, Vector2::new(-3, 4));
+        assert_eq!(a * 3, Vector2::new(9, -12));
+        assert_eq!(a / 2, Vector2::new(1, -2));
+        assert_eq!(a.dot(&b), -5);
+        assert_eq!(a.norm(), 25);
+    }
+
+    #[test]
+    fn vector2_cross() {
+        assert_eq!(Vector2::<i32>::i().cross(&Vector2::j()), 1);
+        assert_eq!(Vector2::<i32>::j().cross(&Vector2::i()), -1);
+        assert_eq!(Vector2::new(2, 4).cross(&Vector2::new(1, 2)), 0);
+    }
+
+    #[test]
+    fn vector2_distances() {
+        let v = Vector2::new(-3, 5);
+        assert_eq!(v.manhattan(), 8);
+        assert_eq!(v.chebyshev(), 5);
+        assert_eq!(v.rem_euclid(&Vector2::new(2, 4)), Vector2::new(1, 1));
+        assert_eq!(Vector2::new(3.0, 4.0).magnitude(), 5.0);
+    }
+
+    #[test]
+    fn vector2_coordinate() {
+        let v: Vector2<isize> = Coordinate(2, 5).into();
+        assert_eq!(v, Vector2::new(5, 2));
+        assert_eq!(Coordinate::from(v), Coordinate(2, 5));
+    }
 
     #[test]
     fn add() {
@@ -33,3 +33,67 @@ impl Cardinal {
         self.left().opposite()
     }
 }
+
+/// The 8 directions of a compass rose, including the diagonals.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum Compass {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl Compass {
+    /// All 8 directions, clockwise from north.
+    pub fn all() -> [Self; 8] {
+        [
+            Self::North,
+            Self::NorthEast,
+            Self::East,
+            Self::SouthEast,
+            Self::South,
+            Self::SouthWest,
+            Self::West,
+            Self::NorthWest,
+        ]
+    }
+
+    /// The 4 diagonal directions, clockwise from northeast.
+    pub fn ordinals() -> [Self; 4] {
+        [
+            Self::NorthEast,
+            Self::SouthEast,
+            Self::SouthWest,
+            Self::NorthWest,
+        ]
+    }
+
+    /// The change in `(row, column)` for one step in this direction.
+    pub fn delta(&self) -> (isize, isize) {
+        match self {
+            Self::North => (-1, 0),
+            Self::NorthEast => (-1, 1),
+            Self::East => (0, 1),
+            Self::SouthEast => (1, 1),
+            Self::South => (1, 0),
+            Self::SouthWest => (1, -1),
+            Self::West => (0, -1),
+            Self::NorthWest => (-1, -1),
+        }
+    }
+}
+
+impl From<Cardinal> for Compass {
+    fn from(value: Cardinal) -> Self {
+        match value {
+            Cardinal::North => Self::North,
+            Cardinal::East => Self::East,
+            Cardinal::South => Self::South,
+            Cardinal::West => Self::West,
+        }
+    }
+}
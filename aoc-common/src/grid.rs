@@ -2,12 +2,16 @@ use std::fmt::{self, Debug};
 use std::hash::Hash;
 use std::str::FromStr;
 use std::{
-    convert::TryFrom,
+    convert::{Infallible, TryFrom},
     ops::{Index, IndexMut},
 };
 
 use crate::direction::Cardinal;
 
+mod sparse;
+
+pub use sparse::SparseGrid;
+
 #[derive(PartialEq, Eq, Clone, Copy, Hash, Default)]
 pub struct Coordinate(pub isize, pub isize);
 
@@ -134,30 +138,125 @@ impl Coordinate {
     }
 }
 
-#[derive(Debug, Clone)]
+/// The smallest rectangle containing a set of coordinates. Both corners are
+/// inclusive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BoundingBox {
+    pub min: Coordinate,
+    pub max: Coordinate,
+}
+
+impl BoundingBox {
+    /// A bounding box containing only `coord`.
+    pub fn new(coord: Coordinate) -> Self {
+        Self {
+            min: coord,
+            max: coord,
+        }
+    }
+
+    /// The bounding box of the given coordinates, or `None` if there are none.
+    ///
+    /// ```
+    /// use aoc_common::grid::{BoundingBox, Coordinate};
+    ///
+    /// let bb = BoundingBox::from_coords([Coordinate(2, 5), Coordinate(-1, 3)]).unwrap();
+    ///
+    /// assert_eq!((bb.min, bb.max), (Coordinate(-1, 3), Coordinate(2, 5)));
+    /// assert_eq!((bb.rows(), bb.cols()), (4, 3));
+    /// ```
+    pub fn from_coords(coords: impl IntoIterator<Item = Coordinate>) -> Option<Self> {
+        let mut coords = coords.into_iter();
+        let mut ret = Self::new(coords.next()?);
+        coords.for_each(|c| ret.include(c));
+        Some(ret)
+    }
+
+    /// Grows the bounding box, if necessary, so that it contains `coord`.
+    pub fn include(&mut self, coord: Coordinate) {
+        self.min = Coordinate(self.min.0.min(coord.0), self.min.1.min(coord.1));
+        self.max = Coordinate(self.max.0.max(coord.0), self.max.1.max(coord.1));
+    }
+
+    pub fn contains(&self, coord: Coordinate) -> bool {
+        (self.min.0..=self.max.0).contains(&coord.0) && (self.min.1..=self.max.1).contains(&coord.1)
+    }
+
+    /// The number of rows spanned by the bounding box.
+    pub fn rows(&self) -> usize {
+        self.max.0.abs_diff(self.min.0) + 1
+    }
+
+    /// The number of columns spanned by the bounding box.
+    pub fn cols(&self) -> usize {
+        self.max.1.abs_diff(self.min.1) + 1
+    }
+}
+
+/// A dense two-dimensional grid with `n` rows and `m` columns.
+///
+/// The cells are stored in a single row-major buffer, so the cell at `(i, j)`
+/// lives at index `i * m + j`. Indexing with a [`Coordinate`] that is out of
+/// bounds panics, as it would for nested vecs, so use [`Grid::get`] or check
+/// [`Grid::is_in_bounds`] first.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Grid<T> {
-    pub grid: Vec<Vec<T>>,
+    cells: Vec<T>,
     pub n: usize,
     pub m: usize,
 }
 
+/// An error from building a [`Grid`] out of rows of text or cells.
+///
+/// Rows and columns are counted from 0, so `row` is also the index of the
+/// offending line when parsing a string.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GridParseError<E = Infallible> {
+    /// The char at `(row, col)` could not be converted into a cell.
+    Cell { row: usize, col: usize, error: E },
+    /// A row has `len` cells, where the first row has `expected`.
+    Ragged {
+        row: usize,
+        len: usize,
+        expected: usize,
+    },
+}
+
+impl<E> GridParseError<E> {
+    /// The row the error is in.
+    pub fn row(&self) -> usize {
+        match self {
+            Self::Cell { row, .. } | Self::Ragged { row, .. } => *row,
+        }
+    }
+}
+
+impl<E> fmt::Display for GridParseError<E>
+where
+    E: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Cell { error, .. } => write!(f, "invalid cell: {}", error),
+            Self::Ragged { len, expected, .. } => write!(
+                f,
+                "expected every row to have {} cells, found {}",
+                expected, len
+            ),
+        }
+    }
+}
+
+impl<E> std::error::Error for GridParseError<E> where E: fmt::Debug + fmt::Display {}
+
 impl<T> FromStr for Grid<T>
 where
     T: TryFrom<char>,
 {
-    type Err = T::Error;
+    type Err = GridParseError<T::Error>;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let grid = s
-            .lines()
-            .map(|line| {
-                line.chars()
-                    .map(|c| T::try_from(c))
-                    .collect::<Result<Vec<T>, T::Error>>()
-            })
-            .collect::<Result<Vec<Vec<T>>, T::Error>>()?;
-
-        Ok(grid.into())
+        Self::parse_lines(s.lines())
     }
 }
 
@@ -165,41 +264,42 @@ impl<T> TryFrom<&[String]> for Grid<T>
 where
     T: TryFrom<char>,
 {
-    type Error = T::Error;
+    type Error = GridParseError<T::Error>;
 
     fn try_from(lines: &[String]) -> Result<Self, Self::Error> {
-        let grid = lines
-            .iter()
-            .map(|line| {
-                line.chars()
-                    .map(|c| T::try_from(c))
-                    .collect::<Result<Vec<T>, T::Error>>()
-            })
-            .collect::<Result<Vec<Vec<T>>, T::Error>>()?;
-
-        Ok(grid.into())
+        Self::parse_lines(lines.iter().map(String::as_str))
     }
 }
 
 impl<T> From<Vec<Vec<T>>> for Grid<T> {
+    /// An empty vec becomes a grid with no rows or columns. Use
+    /// [`Grid::try_from_rows`] for rows that have not been checked.
+    ///
+    /// # Panics
+    ///
+    /// If the rows are not all the same length.
     fn from(grid: Vec<Vec<T>>) -> Self {
-        let n = grid.len();
-        let m = grid[0].len();
-        Self { grid, n, m }
+        match Self::try_from_rows(grid) {
+            Ok(grid) => grid,
+            Err(e) => panic!("every row of a grid must have the same length: {}", e),
+        }
     }
 }
 
 impl<T> Index<Coordinate> for Grid<T> {
     type Output = T;
 
+    #[inline]
     fn index(&self, idx: Coordinate) -> &Self::Output {
-        &self.grid[idx.0 as usize][idx.1 as usize]
+        &self.cells[self.offset(idx)]
     }
 }
 
 impl<T> IndexMut<Coordinate> for Grid<T> {
+    #[inline]
     fn index_mut(&mut self, idx: Coordinate) -> &mut Self::Output {
-        &mut self.grid[idx.0 as usize][idx.1 as usize]
+        let offset = self.offset(idx);
+        &mut self.cells[offset]
     }
 }
 
@@ -208,9 +308,9 @@ where
     T: fmt::Display,
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        for i in 0..self.n {
-            for j in 0..self.m {
-                write!(f, "{}", self[(i, j).into()])?;
+        for row in self.cells.chunks_exact(self.m.max(1)) {
+            for cell in row.iter() {
+                write!(f, "{}", cell)?;
             }
             writeln!(f)?;
         }
@@ -219,13 +319,392 @@ where
     }
 }
 
+impl<T> Grid<T> {
+    /// Builds a grid from its rows, or fails if they are not all the same
+    /// length. An empty vec becomes a grid with no rows or columns.
+    ///
+    /// ```
+    /// use aoc_common::grid::{Grid, GridParseError};
+    ///
+    /// let grid = Grid::try_from_rows(vec![vec![1, 2], vec![3, 4]]).unwrap();
+    /// assert_eq!((grid.n, grid.m), (2, 2));
+    ///
+    /// let err = Grid::try_from_rows(vec![vec![1, 2], vec![3]]).unwrap_err();
+    /// assert_eq!(err, GridParseError::Ragged { row: 1, len: 1, expected: 2 });
+    /// ```
+    pub fn try_from_rows(rows: Vec<Vec<T>>) -> Result<Self, GridParseError> {
+        match Self::ragged_row(&rows) {
+            Some(err) => Err(err),
+            None => Ok(Self::from_rows_unchecked(rows)),
+        }
+    }
+
+    /// The first row whose length differs from the first row's.
+    fn ragged_row<E>(rows: &[Vec<T>]) -> Option<GridParseError<E>> {
+        let expected = rows.first().map_or(0, Vec::len);
+        rows.iter()
+            .enumerate()
+            .find(|(_, r)| r.len() != expected)
+            .map(|(row, r)| GridParseError::Ragged {
+                row,
+                len: r.len(),
+                expected,
+            })
+    }
+
+    fn from_rows_unchecked(rows: Vec<Vec<T>>) -> Self {
+        Self {
+            n: rows.len(),
+            m: rows.first().map_or(0, Vec::len),
+            cells: rows.into_iter().flatten().collect(),
+        }
+    }
+
+    fn parse_lines<'a>(
+        lines: impl Iterator<Item = &'a str>,
+    ) -> Result<Self, GridParseError<T::Error>>
+    where
+        T: TryFrom<char>,
+    {
+        let rows = lines
+            .enumerate()
+            .map(|(row, line)| {
+                line.chars()
+                    .enumerate()
+                    .map(|(col, c)| {
+                        T::try_from(c).map_err(|error| GridParseError::Cell { row, col, error })
+                    })
+                    .collect::<Result<Vec<T>, _>>()
+            })
+            .collect::<Result<Vec<Vec<T>>, _>>()?;
+
+        match Self::ragged_row(&rows) {
+            Some(err) => Err(err),
+            None => Ok(Self::from_rows_unchecked(rows)),
+        }
+    }
+
+    #[inline]
+    fn offset(&self, coord: Coordinate) -> usize {
+        assert!(
+            (0..self.n as isize).contains(&coord.0) && (0..self.m as isize).contains(&coord.1),
+            "{:?} is out of bounds for a {}x{} grid",
+            coord,
+            self.n,
+            self.m
+        );
+        coord.0 as usize * self.m + coord.1 as usize
+    }
+
+    /// The cells of the grid in row-major order.
+    pub fn as_slice(&self) -> &[T] {
+        &self.cells
+    }
+
+    /// The cells of the grid in row-major order.
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        &mut self.cells
+    }
+
+    /// Returns the `i`th row.
+    ///
+    /// # Panics
+    ///
+    /// If `i` is out of bounds.
+    pub fn row(&self, i: usize) -> &[T] {
+        assert!(i < self.n, "row {} is out of bounds for {} rows", i, self.n);
+        &self.cells[i * self.m..(i + 1) * self.m]
+    }
+
+    /// Returns the `i`th row.
+    ///
+    /// # Panics
+    ///
+    /// If `i` is out of bounds.
+    pub fn row_mut(&mut self, i: usize) -> &mut [T] {
+        assert!(i < self.n, "row {} is out of bounds for {} rows", i, self.n);
+        &mut self.cells[i * self.m..(i + 1) * self.m]
+    }
+
+    /// Returns a view of the `j`th column.
+    ///
+    /// # Panics
+    ///
+    /// If `j` is out of bounds.
+    ///
+    /// ```
+    /// use aoc_common::grid::Grid;
+    ///
+    /// let grid: Grid<char> = "abc\ndef".parse().unwrap();
+    /// let col = grid.column(1);
+    ///
+    /// assert_eq!(grid.row(1), &['d', 'e', 'f']);
+    /// assert_eq!(col.len(), 2);
+    /// assert_eq!(col[1], 'e');
+    /// assert_eq!(col.iter().collect::<String>(), "be");
+    /// ```
+    pub fn column(&self, j: usize) -> Column<'_, T> {
+        assert!(
+            j < self.m,
+            "column {} is out of bounds for {} columns",
+            j,
+            self.m
+        );
+        Column {
+            cells: &self.cells,
+            stride: self.m,
+            offset: j,
+        }
+    }
+
+    /// Returns a reference to the cell at `coord`, if it is in bounds.
+    pub fn get_ref(&self, coord: Coordinate) -> Option<&T> {
+        if self.contains(coord) {
+            Some(&self[coord])
+        } else {
+            None
+        }
+    }
+
+    /// Returns a mutable reference to the cell at `coord`, if it is in bounds.
+    pub fn get_mut(&mut self, coord: Coordinate) -> Option<&mut T> {
+        if self.contains(coord) {
+            Some(&mut self[coord])
+        } else {
+            None
+        }
+    }
+
+    fn contains(&self, coord: Coordinate) -> bool {
+        (0..self.n as isize).contains(&coord.0) && (0..self.m as isize).contains(&coord.1)
+    }
+
+    /// Iterates over every cell and its coordinate, in row-major order.
+    pub fn cells(&self) -> impl DoubleEndedIterator<Item = (Coordinate, &T)> + '_ {
+        let m = self.m;
+        self.cells
+            .iter()
+            .enumerate()
+            .map(move |(idx, v)| (Coordinate((idx / m) as isize, (idx % m) as isize), v))
+    }
+
+    /// Iterates mutably over every cell and its coordinate, in row-major
+    /// order.
+    pub fn cells_mut(&mut self) -> impl DoubleEndedIterator<Item = (Coordinate, &mut T)> + '_ {
+        let m = self.m;
+        self.cells
+            .iter_mut()
+            .enumerate()
+            .map(move |(idx, v)| (Coordinate((idx / m) as isize, (idx % m) as isize), v))
+    }
+
+    /// Iterates over the rows, from top to bottom.
+    pub fn rows(&self) -> impl DoubleEndedIterator<Item = &[T]> + ExactSizeIterator + '_ {
+        self.cells.chunks_exact(self.m.max(1))
+    }
+
+    /// Iterates over the columns, from left to right.
+    pub fn columns(
+        &self,
+    ) -> impl DoubleEndedIterator<Item = Column<'_, T>> + ExactSizeIterator + '_ {
+        (0..self.m).map(|j| self.column(j))
+    }
+
+    /// Iterates over the cells from `start` (inclusive) in steps of `step`,
+    /// until the edge of the grid is reached.
+    ///
+    /// ```
+    /// use aoc_common::grid::{Coordinate, Grid};
+    ///
+    /// let grid: Grid<char> = "abc\ndef\nghi".parse().unwrap();
+    /// let line = grid.line(Coordinate(2, 0), Coordinate(-1, 1));
+    ///
+    /// assert_eq!(line.map(|(_, c)| *c).collect::<String>(), "gec");
+    /// ```
+    pub fn line(&self, start: Coordinate, step: Coordinate) -> Line<'_, T> {
+        Line {
+            grid: self,
+            cur: start,
+            step,
+        }
+    }
+
+    /// Iterates over the diagonals running from top-left to bottom-right,
+    /// starting with the one through the bottom-left corner.
+    ///
+    /// ```
+    /// use aoc_common::grid::Grid;
+    ///
+    /// let grid: Grid<char> = "abc\ndef".parse().unwrap();
+    /// let diagonals = grid
+    ///     .diagonals()
+    ///     .map(|d| d.map(|(_, c)| *c).collect::<String>())
+    ///     .collect::<Vec<_>>();
+    ///
+    /// assert_eq!(diagonals, vec!["d", "ae", "bf", "c"]);
+    /// ```
+    pub fn diagonals(&self) -> impl Iterator<Item = Line<'_, T>> + '_ {
+        let (n, m) = (self.n as isize, self.m as isize);
+        let left = (0..n).rev().map(|i| Coordinate(i, 0));
+        let top = (1..m).map(|j| Coordinate(0, j));
+
+        left.chain(top)
+            .map(|start| self.line(start, Coordinate(1, 1)))
+    }
+
+    /// Iterates over the diagonals running from top-right to bottom-left,
+    /// starting with the one through the top-left corner.
+    ///
+    /// ```
+    /// use aoc_common::grid::Grid;
+    ///
+    /// let grid: Grid<char> = "abc\ndef".parse().unwrap();
+    /// let diagonals = grid
+    ///     .anti_diagonals()
+    ///     .map(|d| d.map(|(_, c)| *c).collect::<String>())
+    ///     .collect::<Vec<_>>();
+    ///
+    /// assert_eq!(diagonals, vec!["a", "bd", "ce", "f"]);
+    /// ```
+    pub fn anti_diagonals(&self) -> impl Iterator<Item = Line<'_, T>> + '_ {
+        let (n, m) = (self.n as isize, self.m as isize);
+        let top = (0..m).map(|j| Coordinate(0, j));
+        let right = (1..n).map(move |i| Coordinate(i, m - 1));
+
+        top.chain(right)
+            .map(|start| self.line(start, Coordinate(1, -1)))
+    }
+
+    /// The in-bounds north, south, east and west neighbours of `coord`, in that
+    /// order, along with their values.
+    ///
+    /// ```
+    /// use aoc_common::grid::{Coordinate, Grid};
+    ///
+    /// let grid: Grid<char> = "abc\ndef".parse().unwrap();
+    /// let neighbours = grid
+    ///     .cardinal_neighbours(Coordinate(0, 0))
+    ///     .collect::<Vec<_>>();
+    ///
+    /// assert_eq!(neighbours, vec![(Coordinate(1, 0), &'d'), (Coordinate(0, 1), &'b')]);
+    /// ```
+    pub fn cardinal_neighbours(
+        &self,
+        coord: Coordinate,
+    ) -> impl Iterator<Item = (Coordinate, &T)> + '_ {
+        coord
+            .cardinal_neighbours()
+            .into_iter()
+            .filter_map(|c| self.get_ref(c).map(|v| (c, v)))
+    }
+
+    /// All 8 of the in-bounds neighbours of `coord`, in the same order as
+    /// [`Coordinate::neighbours`], along with their values.
+    pub fn neighbours(&self, coord: Coordinate) -> impl Iterator<Item = (Coordinate, &T)> + '_ {
+        coord
+            .neighbours()
+            .into_iter()
+            .filter_map(|c| self.get_ref(c).map(|v| (c, v)))
+    }
+
+    /// Replaces every cell with `K` cells side by side, so that the new grid
+    /// is `K` times as wide.
+    ///
+    /// ```
+    /// use aoc_common::grid::Grid;
+    ///
+    /// let grid: Grid<char> = "#O.".parse().unwrap();
+    /// let wide = grid.map_cells(|c| match c {
+    ///     'O' => ['[', ']'],
+    ///     c => [*c, *c],
+    /// });
+    ///
+    /// assert_eq!(wide.to_string(), "##[]..\n");
+    /// ```
+    pub fn map_cells<U, const K: usize>(&self, f: impl FnMut(&T) -> [U; K]) -> Grid<U> {
+        Grid {
+            cells: self.cells.iter().flat_map(f).collect(),
+            n: self.n,
+            m: self.m * K,
+        }
+    }
+
+    /// Maps `coord` onto the grid as if its edges wrapped around, so that the
+    /// grid is a torus.
+    ///
+    /// # Panics
+    ///
+    /// If the grid is empty, as there is nothing to wrap onto.
+    ///
+    /// ```
+    /// use aoc_common::grid::{Coordinate, Grid};
+    ///
+    /// let grid = Grid::new(7, 11, 0);
+    ///
+    /// assert_eq!(grid.wrap(Coordinate(-1, 11)), Coordinate(6, 0));
+    /// assert_eq!(grid.wrap(Coordinate(15, -23)), Coordinate(1, 10));
+    /// ```
+    pub fn wrap(&self, coord: Coordinate) -> Coordinate {
+        assert!(
+            self.n > 0 && self.m > 0,
+            "cannot wrap {:?} onto an empty grid",
+            coord
+        );
+        Coordinate(
+            coord.0.rem_euclid(self.n as isize),
+            coord.1.rem_euclid(self.m as isize),
+        )
+    }
+
+    /// Returns the cell at `coord` after wrapping it onto the grid.
+    ///
+    /// # Panics
+    ///
+    /// If the grid is empty.
+    pub fn get_wrapping(&self, coord: Coordinate) -> &T {
+        &self[self.wrap(coord)]
+    }
+
+    /// Like [`Grid::cardinal_neighbours`], but with the edges of the grid
+    /// wrapping around, so there are always 4 neighbours.
+    ///
+    /// # Panics
+    ///
+    /// If the grid is empty, once the iterator is advanced.
+    pub fn cardinal_neighbours_wrapping(
+        &self,
+        coord: Coordinate,
+    ) -> impl Iterator<Item = (Coordinate, &T)> + '_ {
+        coord.cardinal_neighbours().into_iter().map(|c| {
+            let c = self.wrap(c);
+            (c, &self[c])
+        })
+    }
+
+    /// Like [`Grid::neighbours`], but with the edges of the grid wrapping
+    /// around, so there are always 8 neighbours.
+    ///
+    /// # Panics
+    ///
+    /// If the grid is empty, once the iterator is advanced.
+    pub fn neighbours_wrapping(
+        &self,
+        coord: Coordinate,
+    ) -> impl Iterator<Item = (Coordinate, &T)> + '_ {
+        coord.neighbours().into_iter().map(|c| {
+            let c = self.wrap(c);
+            (c, &self[c])
+        })
+    }
+}
+
 impl<T> Grid<T>
 where
     T: Copy + PartialEq,
 {
     pub fn new(n: usize, m: usize, default: T) -> Self {
         Self {
-            grid: vec![vec![default; m]; n],
+            cells: vec![default; n * m],
             n,
             m,
         }
@@ -249,7 +728,7 @@ where
     }
 
     pub fn is_in_bounds(&self, coord: Coordinate) -> bool {
-        (0..self.n as isize).contains(&coord.0) && (0..self.m as isize).contains(&coord.1)
+        self.contains(coord)
     }
 
     pub fn is_on_edge(&self, coord: Coordinate) -> bool {
@@ -263,14 +742,359 @@ where
     }
 
     pub fn find_coordinate(&self, pred: impl Fn(&T) -> bool) -> Option<Coordinate> {
-        for i in 0..self.n {
-            for j in 0..self.m {
-                if pred(&self.grid[i][j]) {
-                    return Some(Coordinate(i as isize, j as isize));
-                }
+        self.cells
+            .iter()
+            .position(pred)
+            .map(|idx| Coordinate((idx / self.m) as isize, (idx % self.m) as isize))
+    }
+}
+
+/// Geometric transformations, each of which returns a new grid.
+impl<T> Grid<T>
+where
+    T: Clone,
+{
+    /// Builds an `n` by `m` grid where the cell at `(i, j)` is a copy of the
+    /// cell at `source(i, j)` in this grid.
+    fn remap(&self, n: usize, m: usize, source: impl Fn(usize, usize) -> (usize, usize)) -> Self {
+        let cells = (0..n)
+            .flat_map(|i| (0..m).map(move |j| (i, j)))
+            .map(|(i, j)| {
+                let (si, sj) = source(i, j);
+                self.cells[si * self.m + sj].clone()
+            })
+            .collect();
+
+        Self { cells, n, m }
+    }
+
+    /// Swaps the rows and columns.
+    ///
+    /// ```
+    /// use aoc_common::grid::Grid;
+    ///
+    /// let grid: Grid<char> = "abc\ndef".parse().unwrap();
+    ///
+    /// assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf\n");
+    /// ```
+    pub fn transpose(&self) -> Self {
+        self.remap(self.m, self.n, |i, j| (j, i))
+    }
+
+    /// Rotates the grid a quarter turn clockwise.
+    ///
+    /// ```
+    /// use aoc_common::grid::Grid;
+    ///
+    /// let grid: Grid<char> = "abc\ndef".parse().unwrap();
+    ///
+    /// assert_eq!(grid.rotate_cw().to_string(), "da\neb\nfc\n");
+    /// ```
+    pub fn rotate_cw(&self) -> Self {
+        let n = self.n;
+        self.remap(self.m, self.n, |i, j| (n - 1 - j, i))
+    }
+
+    /// Rotates the grid a quarter turn anticlockwise.
+    ///
+    /// ```
+    /// use aoc_common::grid::Grid;
+    ///
+    /// let grid: Grid<char> = "abc\ndef".parse().unwrap();
+    ///
+    /// assert_eq!(grid.rotate_ccw().to_string(), "cf\nbe\nad\n");
+    /// ```
+    pub fn rotate_ccw(&self) -> Self {
+        let m = self.m;
+        self.remap(self.m, self.n, |i, j| (j, m - 1 - i))
+    }
+
+    /// Mirrors the grid left to right, reversing every row.
+    pub fn flip_horizontal(&self) -> Self {
+        let m = self.m;
+        self.remap(self.n, self.m, |i, j| (i, m - 1 - j))
+    }
+
+    /// Mirrors the grid top to bottom, reversing the order of the rows.
+    pub fn flip_vertical(&self) -> Self {
+        let n = self.n;
+        self.remap(self.n, self.m, |i, j| (n - 1 - i, j))
+    }
+
+    /// The grid rotated by 0, 1, 2 and 3 quarter turns clockwise.
+    pub fn rotations(&self) -> [Self; 4] {
+        let r1 = self.rotate_cw();
+        let r2 = r1.rotate_cw();
+        let r3 = r2.rotate_cw();
+        [self.clone(), r1, r2, r3]
+    }
+
+    /// All 8 rotations and reflections of the grid: the four
+    /// [rotations](Grid::rotations), followed by the four rotations of the
+    /// transpose.
+    ///
+    /// Symmetric grids will produce duplicates.
+    pub fn orientations(&self) -> [Self; 8] {
+        let [a, b, c, d] = self.rotations();
+        let [e, f, g, h] = self.transpose().rotations();
+        [a, b, c, d, e, f, g, h]
+    }
+
+    /// Copies the cells inside `rect` (inclusive) into a new grid, or returns
+    /// `None` if `rect` is not entirely within the grid or its corners are
+    /// the wrong way round.
+    ///
+    /// ```
+    /// use aoc_common::grid::{BoundingBox, Coordinate, Grid};
+    ///
+    /// let grid: Grid<char> = "abc\ndef\nghi".parse().unwrap();
+    /// let rect = BoundingBox { min: Coordinate(1, 1), max: Coordinate(2, 2) };
+    ///
+    /// assert_eq!(grid.subgrid(rect).unwrap().to_string(), "ef\nhi\n");
+    /// assert!(grid.subgrid(BoundingBox::new(Coordinate(3, 0))).is_none());
+    ///
+    /// let inverted = BoundingBox { min: Coordinate(2, 2), max: Coordinate(0, 0) };
+    /// assert!(grid.subgrid(inverted).is_none());
+    /// ```
+    pub fn subgrid(&self, rect: BoundingBox) -> Option<Self> {
+        if rect.min.0 > rect.max.0 || rect.min.1 > rect.max.1 {
+            return None;
+        }
+
+        if !(self.contains(rect.min) && self.contains(rect.max)) {
+            return None;
+        }
+
+        let (di, dj) = (rect.min.0 as usize, rect.min.1 as usize);
+        Some(self.remap(rect.rows(), rect.cols(), |i, j| (i + di, j + dj)))
+    }
+
+    /// Surrounds the grid with a border `border` cells wide, filled with
+    /// `value`.
+    ///
+    /// ```
+    /// use aoc_common::grid::Grid;
+    ///
+    /// let grid: Grid<char> = "ab".parse().unwrap();
+    ///
+    /// assert_eq!(grid.pad(1, '#').to_string(), "####\n#ab#\n####\n");
+    /// ```
+    pub fn pad(&self, border: usize, value: T) -> Self {
+        let (n, m) = (self.n + 2 * border, self.m + 2 * border);
+        let mut cells = Vec::with_capacity(n * m);
+
+        cells.resize(border * m, value.clone());
+        for row in self.rows() {
+            cells.extend(std::iter::repeat_n(value.clone(), border));
+            cells.extend_from_slice(row);
+            cells.extend(std::iter::repeat_n(value.clone(), border));
+        }
+        cells.resize(n * m, value);
+
+        Self { cells, n, m }
+    }
+}
+
+/// A view of a single column of a [`Grid`].
+#[derive(Debug, Clone, Copy)]
+pub struct Column<'a, T> {
+    cells: &'a [T],
+    stride: usize,
+    offset: usize,
+}
+
+impl<'a, T> Column<'a, T> {
+    pub fn len(&self) -> usize {
+        self.cells.len() / self.stride
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn get(&self, i: usize) -> Option<&'a T> {
+        self.cells.get(i * self.stride + self.offset)
+    }
+
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &'a T> + ExactSizeIterator {
+        self.cells[self.offset..].iter().step_by(self.stride)
+    }
+}
+
+impl<T> Index<usize> for Column<'_, T> {
+    type Output = T;
+
+    fn index(&self, i: usize) -> &Self::Output {
+        self.get(i).expect("index out of bounds for column")
+    }
+}
+
+/// An iterator over the cells along a straight line in a [`Grid`], created by
+/// [`Grid::line`], [`Grid::diagonals`] and [`Grid::anti_diagonals`].
+#[derive(Debug, Clone)]
+pub struct Line<'a, T> {
+    grid: &'a Grid<T>,
+    cur: Coordinate,
+    step: Coordinate,
+}
+
+impl<'a, T> Iterator for Line<'a, T> {
+    type Item = (Coordinate, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let coord = self.cur;
+        let value = self.grid.get_ref(coord)?;
+        self.cur = Coordinate(coord.0 + self.step.0, coord.1 + self.step.1);
+        Some((coord, value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq)]
+    struct Digit(u32);
+
+    impl TryFrom<char> for Digit {
+        type Error = char;
+
+        fn try_from(value: char) -> Result<Self, Self::Error> {
+            value.to_digit(10).map(Digit).ok_or(value)
+        }
+    }
+
+    #[test]
+    fn neighbours_test() {
+        let grid: Grid<char> = "abc\ndef\nghi".parse().unwrap();
+
+        let corner = grid.neighbours(Coordinate(0, 0)).collect::<Vec<_>>();
+        assert_eq!(corner.len(), 3);
+        assert!(corner.contains(&(Coordinate(1, 1), &'e')));
+
+        assert_eq!(grid.neighbours(Coordinate(1, 1)).count(), 8);
+        assert_eq!(grid.cardinal_neighbours(Coordinate(0, 2)).count(), 2);
+    }
+
+    #[test]
+    fn wrapping_neighbours_test() {
+        let grid: Grid<char> = "abc\ndef\nghi".parse().unwrap();
+
+        let mut values = grid
+            .cardinal_neighbours_wrapping(Coordinate(0, 0))
+            .map(|(_, c)| *c)
+            .collect::<Vec<_>>();
+        values.sort();
+        assert_eq!(values, vec!['b', 'c', 'd', 'g']);
+
+        assert_eq!(grid.neighbours_wrapping(Coordinate(2, 2)).count(), 8);
+        assert_eq!(*grid.get_wrapping(Coordinate(-1, -1)), 'i');
+    }
+
+    #[test]
+    fn empty_grid_test() {
+        let grid: Grid<char> = "".parse().unwrap();
+
+        assert_eq!((grid.n, grid.m), (0, 0));
+        assert_eq!(grid.cells().count(), 0);
+        assert!(!grid.is_in_bounds(Coordinate(0, 0)));
+        assert_eq!(grid.to_string(), "");
+    }
+
+    #[test]
+    fn parse_error_test() {
+        let err = "abc\nde\nfgh".parse::<Grid<char>>().unwrap_err();
+        assert_eq!(
+            err,
+            GridParseError::Ragged {
+                row: 1,
+                len: 2,
+                expected: 3
             }
+        );
+        assert_eq!(
+            err.to_string(),
+            "expected every row to have 3 cells, found 2"
+        );
+
+        let err = "12\n3x".parse::<Grid<Digit>>().unwrap_err();
+        assert_eq!(err.row(), 1);
+        assert!(matches!(err, GridParseError::Cell { row: 1, col: 1, .. }));
+
+        let lines = vec!["ab".to_string(), "abc".to_string()];
+        assert!(Grid::<char>::try_from(lines.as_slice()).is_err());
+    }
+
+    #[test]
+    #[should_panic(expected = "(0, 3) is out of bounds for a 2x3 grid")]
+    fn index_out_of_bounds_test() {
+        let grid: Grid<char> = "abc\ndef".parse().unwrap();
+        let _ = grid[Coordinate(0, 3)];
+    }
+
+    #[test]
+    #[should_panic(expected = "every row of a grid must have the same length")]
+    fn ragged_from_test() {
+        let _ = Grid::from(vec![vec![1, 2], vec![3]]);
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot wrap (1, 1) onto an empty grid")]
+    fn wrap_empty_grid_test() {
+        let grid: Grid<char> = "".parse().unwrap();
+        grid.wrap(Coordinate(1, 1));
+    }
+
+    #[test]
+    fn transform_test() {
+        let grid: Grid<char> = "abc\ndef".parse().unwrap();
+
+        assert_eq!(grid.flip_horizontal().to_string(), "cba\nfed\n");
+        assert_eq!(grid.flip_vertical().to_string(), "def\nabc\n");
+        assert_eq!(
+            grid.rotate_cw().rotate_cw(),
+            grid.flip_horizontal().flip_vertical()
+        );
+        assert_eq!(grid.rotate_cw().rotate_ccw(), grid);
+        assert_eq!(grid.transpose().transpose(), grid);
+
+        let orientations = grid.orientations();
+        for (i, a) in orientations.iter().enumerate() {
+            assert!(orientations[i + 1..].iter().all(|b| a != b));
         }
+        assert!(orientations.contains(&grid.flip_horizontal()));
+        assert!(orientations.contains(&grid.flip_vertical()));
 
-        None
+        let padded = grid.pad(2, '.');
+        assert_eq!((padded.n, padded.m), (6, 7));
+        let inner = BoundingBox {
+            min: Coordinate(2, 2),
+            max: Coordinate(3, 4),
+        };
+        assert_eq!(padded.subgrid(inner), Some(grid));
+    }
+
+    #[test]
+    fn iterators_test() {
+        let mut grid: Grid<char> = "abc\ndef".parse().unwrap();
+
+        assert_eq!(grid.cells().nth(4), Some((Coordinate(1, 1), &'e')));
+        assert_eq!(
+            grid.rows().collect::<Vec<_>>(),
+            vec![&['a', 'b', 'c'], &['d', 'e', 'f']]
+        );
+        assert_eq!(
+            grid.columns()
+                .map(|c| c.iter().collect::<String>())
+                .collect::<Vec<_>>(),
+            vec!["ad", "be", "cf"]
+        );
+
+        for (coord, c) in grid.cells_mut() {
+            if coord.0 == coord.1 {
+                *c = '#';
+            }
+        }
+        assert_eq!(grid.to_string(), "#bc\nd#f\n");
     }
 }
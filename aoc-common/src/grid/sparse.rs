@@ -0,0 +1,276 @@
+use std::{fmt, ops::Index};
+
+use rustc_hash::FxHashMap;
+
+use super::{BoundingBox, Coordinate, Grid};
+
+/// An unbounded grid that only stores the cells that have been set, so it can
+/// grow in any direction, including into negative coordinates.
+///
+/// The bounding box of the occupied cells is kept up to date as cells are
+/// inserted and removed.
+///
+/// ```
+/// use aoc_common::grid::{Coordinate, SparseGrid};
+///
+/// let mut grid = SparseGrid::default();
+/// grid.insert(Coordinate(0, 0), '#');
+/// grid.insert(Coordinate(-1, 2), '#');
+///
+/// assert_eq!(grid.len(), 2);
+/// assert_eq!(grid.get(Coordinate(-1, 2)), Some(&'#'));
+/// assert_eq!(grid.to_string(), "..#\n#..\n");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SparseGrid<T> {
+    cells: FxHashMap<Coordinate, T>,
+    bounding_box: Option<BoundingBox>,
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> Self {
+        Self {
+            cells: FxHashMap::default(),
+            bounding_box: None,
+        }
+    }
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn contains(&self, coord: Coordinate) -> bool {
+        self.cells.contains_key(&coord)
+    }
+
+    pub fn get(&self, coord: Coordinate) -> Option<&T> {
+        self.cells.get(&coord)
+    }
+
+    pub fn get_mut(&mut self, coord: Coordinate) -> Option<&mut T> {
+        self.cells.get_mut(&coord)
+    }
+
+    /// Sets the cell at `coord`, returning its previous value, if any.
+    pub fn insert(&mut self, coord: Coordinate, value: T) -> Option<T> {
+        match self.bounding_box {
+            Some(ref mut bb) => bb.include(coord),
+            None => self.bounding_box = Some(BoundingBox::new(coord)),
+        }
+
+        self.cells.insert(coord, value)
+    }
+
+    /// Clears the cell at `coord`, returning its value, if any.
+    ///
+    /// Removing a cell on the edge of the bounding box means that the
+    /// bounding box has to be recomputed, which is linear in the number of
+    /// cells.
+    pub fn remove(&mut self, coord: Coordinate) -> Option<T> {
+        let ret = self.cells.remove(&coord)?;
+
+        let on_edge = self.bounding_box.is_some_and(|bb| {
+            coord.0 == bb.min.0 || coord.0 == bb.max.0 || coord.1 == bb.min.1 || coord.1 == bb.max.1
+        });
+        if on_edge {
+            self.bounding_box = BoundingBox::from_coords(self.cells.keys().copied());
+        }
+
+        Some(ret)
+    }
+
+    pub fn clear(&mut self) {
+        self.cells.clear();
+        self.bounding_box = None;
+    }
+
+    /// The smallest rectangle containing every occupied cell, or `None` if
+    /// the grid is empty.
+    pub fn bounding_box(&self) -> Option<BoundingBox> {
+        self.bounding_box
+    }
+
+    /// Iterates over the occupied cells in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (Coordinate, &T)> + '_ {
+        self.cells.iter().map(|(c, v)| (*c, v))
+    }
+
+    /// Iterates mutably over the occupied cells in no particular order.
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (Coordinate, &mut T)> + '_ {
+        self.cells.iter_mut().map(|(c, v)| (*c, v))
+    }
+
+    pub fn coordinates(&self) -> impl Iterator<Item = Coordinate> + '_ {
+        self.cells.keys().copied()
+    }
+
+    /// The in-bounds north, south, east and west neighbours of `coord` that
+    /// are occupied, along with their values.
+    pub fn cardinal_neighbours(
+        &self,
+        coord: Coordinate,
+    ) -> impl Iterator<Item = (Coordinate, &T)> + '_ {
+        coord
+            .cardinal_neighbours()
+            .into_iter()
+            .filter_map(|c| self.get(c).map(|v| (c, v)))
+    }
+
+    /// All 8 of the neighbours of `coord` that are occupied, along with their
+    /// values.
+    pub fn neighbours(&self, coord: Coordinate) -> impl Iterator<Item = (Coordinate, &T)> + '_ {
+        coord
+            .neighbours()
+            .into_iter()
+            .filter_map(|c| self.get(c).map(|v| (c, v)))
+    }
+}
+
+impl<T: Clone> SparseGrid<T> {
+    /// Copies the bounding box into a dense [`Grid`], filling unoccupied cells
+    /// with `fill`. The top-left corner of the bounding box becomes `(0, 0)`.
+    ///
+    /// An empty sparse grid becomes an empty grid.
+    pub fn to_grid(&self, fill: T) -> Grid<T> {
+        let Some(bb) = self.bounding_box else {
+            return Grid::from(Vec::<Vec<T>>::new());
+        };
+
+        let grid = (bb.min.0..=bb.max.0)
+            .map(|i| {
+                (bb.min.1..=bb.max.1)
+                    .map(|j| {
+                        self.get(Coordinate(i, j))
+                            .cloned()
+                            .unwrap_or_else(|| fill.clone())
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        grid.into()
+    }
+}
+
+impl<T> Index<Coordinate> for SparseGrid<T> {
+    type Output = T;
+
+    /// # Panics
+    ///
+    /// If the cell at `coord` is not occupied.
+    fn index(&self, coord: Coordinate) -> &Self::Output {
+        &self.cells[&coord]
+    }
+}
+
+impl<T> FromIterator<(Coordinate, T)> for SparseGrid<T> {
+    fn from_iter<I: IntoIterator<Item = (Coordinate, T)>>(iter: I) -> Self {
+        let mut ret = Self::default();
+        ret.extend(iter);
+        ret
+    }
+}
+
+impl<T> Extend<(Coordinate, T)> for SparseGrid<T> {
+    fn extend<I: IntoIterator<Item = (Coordinate, T)>>(&mut self, iter: I) {
+        for (coord, value) in iter {
+            self.insert(coord, value);
+        }
+    }
+}
+
+impl<T> From<Grid<T>> for SparseGrid<T> {
+    /// Every cell of the grid becomes an occupied cell.
+    fn from(grid: Grid<T>) -> Self {
+        let m = grid.m;
+        grid.cells
+            .into_iter()
+            .enumerate()
+            .map(|(idx, v)| (Coordinate((idx / m) as isize, (idx % m) as isize), v))
+            .collect()
+    }
+}
+
+impl<T> fmt::Display for SparseGrid<T>
+where
+    T: fmt::Display,
+{
+    /// Renders the bounding box like a [`Grid`], with unoccupied cells shown
+    /// as `.`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Some(bb) = self.bounding_box else {
+            return Ok(());
+        };
+
+        for i in bb.min.0..=bb.max.0 {
+            for j in bb.min.1..=bb.max.1 {
+                match self.get(Coordinate(i, j)) {
+                    Some(cell) => write!(f, "{}", cell)?,
+                    None => write!(f, ".")?,
+                }
+            }
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bounding_box_test() {
+        let mut grid: SparseGrid<u8> = [(Coordinate(-3, 4), 1), (Coordinate(2, -1), 2)]
+            .into_iter()
+            .collect();
+        grid.insert(Coordinate(0, 0), 3);
+
+        assert_eq!(
+            grid.bounding_box(),
+            Some(BoundingBox {
+                min: Coordinate(-3, -1),
+                max: Coordinate(2, 4)
+            })
+        );
+
+        grid.remove(Coordinate(2, -1));
+        assert_eq!(
+            grid.bounding_box(),
+            Some(BoundingBox {
+                min: Coordinate(-3, 0),
+                max: Coordinate(0, 4)
+            })
+        );
+
+        grid.remove(Coordinate(-3, 4));
+        grid.remove(Coordinate(0, 0));
+        assert!(grid.is_empty());
+        assert_eq!(grid.bounding_box(), None);
+        assert_eq!(grid.to_string(), "");
+    }
+
+    #[test]
+    fn grid_round_trip_test() {
+        let grid: Grid<char> = "#.\n.#\n##".parse().unwrap();
+        let sparse = SparseGrid::from(grid.clone());
+
+        assert_eq!(sparse.len(), 6);
+        assert_eq!(sparse.to_grid('?'), grid);
+        assert_eq!(sparse.to_string(), grid.to_string());
+
+        let empty = SparseGrid::<char>::new().to_grid('.');
+        assert_eq!((empty.n, empty.m), (0, 0));
+    }
+}
@@ -1,15 +1,45 @@
+//! Half-open intervals of integers, and sets of them.
+//!
+//! An [`Interval`] covers `start..end`, and [`Intervals`] keeps a sorted list
+//! of disjoint, non-adjacent intervals so that set operations and membership
+//! queries are linear or logarithmic in the number of intervals rather than
+//! the number of values they cover.
+//!
+//! ```
+//! use aoc_common::interval::{Interval, Intervals};
+//!
+//! let fresh: Intervals<u64> = [(3, 6), (10, 15), (12, 19)].into_iter().map(Interval::from).collect();
+//! assert_eq!(fresh.iter().count(), 2);
+//! assert_eq!(fresh.len(), 12);
+//! assert!(fresh.contains(17));
+//!
+//! let eaten = Intervals::new(vec![Interval::new(4, 11)]);
+//! assert_eq!(
+//!     fresh.difference(&eaten),
+//!     Intervals::new(vec![Interval::new(3, 4), Interval::new(11, 19)])
+//! );
+//! assert_eq!(
+//!     fresh.gaps().collect::<Vec<_>>(),
+//!     vec![Interval::new(6, 10)]
+//! );
+//! ```
+use num::PrimInt;
+
+mod allocator;
+pub use allocator::Allocator;
+
 #[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Ord, PartialOrd)]
-pub struct Interval {
-    pub start: isize,
-    pub end: isize,
+pub struct Interval<T = isize> {
+    pub start: T,
+    pub end: T,
 }
 
-impl Interval {
-    pub fn new(start: isize, end: isize) -> Self {
+impl<T: PrimInt> Interval<T> {
+    pub fn new(start: T, end: T) -> Self {
         Self { start, end }
     }
 
-    pub fn split(&self, x: isize) -> Option<(Interval, Interval)> {
+    pub fn split(&self, x: T) -> Option<(Self, Self)> {
         if self.contains(x) {
             Some((Self::new(self.start, x), Self::new(x, self.end)))
         } else {
@@ -17,7 +47,7 @@ impl Interval {
         }
     }
 
-    pub fn split_unchecked(&self, x: isize) -> (Interval, Interval) {
+    pub fn split_unchecked(&self, x: T) -> (Self, Self) {
         if self.greater_than(x) {
             (Self::new(self.start, self.start), *self)
         } else if self.less_than(x) {
@@ -27,67 +57,218 @@ impl Interval {
         }
     }
 
-    pub fn less_than(&self, x: isize) -> bool {
+    pub fn less_than(&self, x: T) -> bool {
         self.end <= x
     }
 
-    pub fn greater_than(&self, x: isize) -> bool {
+    pub fn greater_than(&self, x: T) -> bool {
         self.start > x
     }
 
+    /// The number of values in the interval, saturating at `usize::MAX`.
     pub fn len(&self) -> usize {
-        (self.end - self.start) as usize
+        if self.is_empty() {
+            0
+        } else {
+            let len = match self.end.checked_sub(&self.start) {
+                Some(len) => len.to_usize(),
+                // only possible for a signed `T`, which always fits in an
+                // i128
+                None => self
+                    .start
+                    .to_i128()
+                    .zip(self.end.to_i128())
+                    .and_then(|(start, end)| end.checked_sub(start))
+                    .and_then(|len| usize::try_from(len).ok()),
+            };
+
+            len.unwrap_or(usize::MAX)
+        }
     }
 
     pub fn is_empty(&self) -> bool {
-        self.len() == 0
+        self.end <= self.start
     }
 
-    pub fn contains(&self, x: isize) -> bool {
-        (self.start..self.end).contains(&x)
+    pub fn contains(&self, x: T) -> bool {
+        self.start <= x && x < self.end
     }
 
-    pub fn intersection(&self, other: &Interval) -> Option<Interval> {
+    /// Whether every value in `other` is also in this interval. An empty
+    /// interval is contained in any other.
+    pub fn contains_interval(&self, other: &Self) -> bool {
+        other.is_empty() || (self.start <= other.start && other.end <= self.end)
+    }
+
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
         if self.end <= other.start || other.end <= self.start {
             None
         } else {
-            Some(Interval::new(
+            Some(Self::new(
                 self.start.max(other.start),
                 self.end.min(other.end),
             ))
         }
     }
 
-    pub fn sum(&self) -> isize {
-        (self.start + self.end - 1) * self.len() as isize / 2
+    /// The sum of the values in the interval.
+    pub fn sum(&self) -> T {
+        if self.is_empty() {
+            return T::zero();
+        }
+
+        (self.start + self.end - T::one()) * (self.end - self.start) / (T::one() + T::one())
     }
 }
 
-impl From<(isize, isize)> for Interval {
-    fn from(value: (isize, isize)) -> Self {
+impl<T: PrimInt> From<(T, T)> for Interval<T> {
+    fn from(value: (T, T)) -> Self {
         Self::new(value.0, value.1)
     }
 }
 
-#[derive(Debug, Clone, Default, PartialEq, Eq)]
-pub struct Intervals {
-    intervals: Vec<Interval>,
+/// A set of values, stored as sorted, disjoint and non-adjacent intervals.
+///
+/// [`Intervals::new`] trusts that its intervals already have this form; use
+/// [`Intervals::add`] or collect an iterator of intervals otherwise.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Intervals<T = isize> {
+    intervals: Vec<Interval<T>>,
 }
 
-impl Intervals {
-    pub fn new(intervals: Vec<Interval>) -> Self {
+impl<T> Default for Intervals<T> {
+    fn default() -> Self {
+        Self {
+            intervals: Vec::default(),
+        }
+    }
+}
+
+impl<T: PrimInt> Intervals<T> {
+    pub fn new(intervals: Vec<Interval<T>>) -> Self {
         Self { intervals }
     }
 
+    /// The number of values in the set, saturating at `usize::MAX`.
     pub fn len(&self) -> usize {
-        self.intervals.iter().map(|x| x.len()).sum()
+        self.intervals
+            .iter()
+            .fold(0, |acc, x| acc.saturating_add(x.len()))
     }
 
     pub fn is_empty(&self) -> bool {
         self.len() == 0
     }
 
-    pub fn split(&self, x: isize) -> (Self, Self) {
+    /// The intervals in the set, in order.
+    pub fn iter(&self) -> std::slice::Iter<'_, Interval<T>> {
+        self.intervals.iter()
+    }
+
+    /// The interval containing `x`, found by binary search.
+    pub fn find(&self, x: T) -> Option<Interval<T>> {
+        let index = self.intervals.partition_point(|i| i.end <= x);
+        self.intervals.get(index).filter(|i| i.contains(x)).copied()
+    }
+
+    /// Whether `x` is in the set, found by binary search.
+    pub fn contains(&self, x: T) -> bool {
+        self.find(x).is_some()
+    }
+
+    /// Whether every value in `interval` is in the set.
+    pub fn contains_interval(&self, interval: &Interval<T>) -> bool {
+        interval.is_empty()
+            || self
+                .find(interval.start)
+                .is_some_and(|i| i.contains_interval(interval))
+    }
+
+    /// The intervals between consecutive intervals in the set.
+    pub fn gaps(&self) -> impl Iterator<Item = Interval<T>> + '_ {
+        self.intervals
+            .windows(2)
+            .map(|w| Interval::new(w[0].end, w[1].start))
+    }
+
+    /// The values in `bound` that are not in the set.
+    pub fn complement(&self, bound: Interval<T>) -> Self {
+        Self::new(vec![bound]).difference(self)
+    }
+
+    /// The values in either set.
+    pub fn union(&self, other: &Self) -> Self {
+        let mut ret = Vec::with_capacity(self.intervals.len() + other.intervals.len());
+        let (mut a, mut b) = (self.iter().peekable(), other.iter().peekable());
+
+        loop {
+            let next = match (a.peek(), b.peek()) {
+                (Some(x), Some(y)) if x.start <= y.start => a.next(),
+                (Some(_), Some(_)) => b.next(),
+                (Some(_), None) => a.next(),
+                (None, _) => b.next(),
+            };
+
+            match next {
+                Some(i) => push_merged(&mut ret, *i),
+                None => return Self::new(ret),
+            }
+        }
+    }
+
+    /// The values in both sets.
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut ret = Vec::default();
+        let (mut i, mut j) = (0, 0);
+
+        while i < self.intervals.len() && j < other.intervals.len() {
+            let (a, b) = (self.intervals[i], other.intervals[j]);
+            if let Some(x) = a.intersection(&b) {
+                ret.push(x);
+            }
+
+            if a.end <= b.end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+
+        Self::new(ret)
+    }
+
+    /// The values in this set that are not in `other`.
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut ret = Vec::default();
+        let mut j = 0;
+
+        for interval in self.iter() {
+            let mut start = interval.start;
+
+            // skip the intervals of other that end before this one starts
+            while j < other.intervals.len() && other.intervals[j].end <= start {
+                j += 1;
+            }
+
+            let mut k = j;
+            while k < other.intervals.len() && other.intervals[k].start < interval.end {
+                let hole = other.intervals[k];
+                if hole.start > start {
+                    ret.push(Interval::new(start, hole.start));
+                }
+                start = start.max(hole.end);
+                k += 1;
+            }
+
+            if start < interval.end {
+                ret.push(Interval::new(start, interval.end));
+            }
+        }
+
+        Self::new(ret)
+    }
+
+    pub fn split(&self, x: T) -> (Self, Self) {
         let index = self.intervals.partition_point(|i| i.end <= x);
 
         if index == self.intervals.len() {
@@ -114,7 +295,7 @@ impl Intervals {
         }
     }
 
-    pub fn add(&mut self, interval: Interval) {
+    pub fn add(&mut self, interval: Interval<T>) {
         if self.intervals.is_empty()
             || self.intervals[self.intervals.len() - 1].end < interval.start
         {
@@ -149,10 +330,74 @@ impl Intervals {
     }
 }
 
+/// Appends an interval to a sorted list, merging it with the last interval if
+/// they overlap or touch.
+fn push_merged<T: PrimInt>(intervals: &mut Vec<Interval<T>>, interval: Interval<T>) {
+    if interval.is_empty() {
+        return;
+    }
+
+    match intervals.last_mut() {
+        Some(last) if interval.start <= last.end => last.end = last.end.max(interval.end),
+        _ => intervals.push(interval),
+    }
+}
+
+impl<T: PrimInt> FromIterator<Interval<T>> for Intervals<T> {
+    fn from_iter<I: IntoIterator<Item = Interval<T>>>(iter: I) -> Self {
+        let mut intervals = iter.into_iter().collect::<Vec<_>>();
+        intervals.sort_unstable();
+
+        let mut ret = Vec::with_capacity(intervals.len());
+        for interval in intervals {
+            push_merged(&mut ret, interval);
+        }
+
+        Self::new(ret)
+    }
+}
+
+impl<T> IntoIterator for Intervals<T> {
+    type Item = Interval<T>;
+    type IntoIter = std::vec::IntoIter<Interval<T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.intervals.into_iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a Intervals<T> {
+    type Item = &'a Interval<T>;
+    type IntoIter = std::slice::Iter<'a, Interval<T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.intervals.iter()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn interval_len_test() {
+        assert_eq!(Interval::new(3, 10).len(), 7);
+        assert_eq!(Interval::new(10, 3).len(), 0);
+        assert_eq!(Interval::<i8>::new(-100, 100).len(), 200);
+        assert_eq!(Interval::<i8>::new(i8::MIN, i8::MAX).len(), 255);
+        assert_eq!(Interval::<u128>::new(1, u128::MAX).len(), usize::MAX);
+        assert_eq!(
+            Interval::<i128>::new(i128::MIN, i128::MAX).len(),
+            usize::MAX
+        );
+
+        let intervals = Intervals::new(vec![
+            Interval::<i128>::new(i128::MIN, 0),
+            Interval::new(1, i128::MAX),
+        ]);
+        assert_eq!(intervals.len(), usize::MAX);
+    }
+
     #[test]
     fn interval_split_test() {
         let interval = Interval::new(1, 10);
@@ -335,4 +580,94 @@ mod tests {
             Some(Interval::new(5, 7))
         );
     }
+
+    fn intervals(v: &[(i32, i32)]) -> Intervals<i32> {
+        Intervals::new(v.iter().map(|&x| Interval::from(x)).collect())
+    }
+
+    #[test]
+    fn intervals_from_iter_test() {
+        let set: Intervals<i32> = [(5, 8), (0, 2), (1, 3), (8, 9), (4, 4), (11, 12)]
+            .into_iter()
+            .map(Interval::from)
+            .collect();
+        assert_eq!(set, intervals(&[(0, 3), (5, 9), (11, 12)]));
+        assert_eq!(set.len(), 8);
+        assert!(Intervals::<i32>::default().is_empty());
+    }
+
+    #[test]
+    fn intervals_contains_test() {
+        let set = intervals(&[(-5, 0), (3, 7), (10, 11)]);
+
+        assert_eq!(set.find(4), Some(Interval::new(3, 7)));
+        assert!(set.contains(-5));
+        assert!(!set.contains(0));
+        assert!(!set.contains(11));
+        assert!(!set.contains(-100));
+
+        assert!(set.contains_interval(&Interval::new(3, 7)));
+        assert!(set.contains_interval(&Interval::new(4, 6)));
+        assert!(!set.contains_interval(&Interval::new(-1, 4)));
+        assert!(!set.contains_interval(&Interval::new(6, 8)));
+        assert!(set.contains_interval(&Interval::new(100, 100)));
+    }
+
+    #[test]
+    fn intervals_union_test() {
+        let a = intervals(&[(0, 3), (6, 9), (20, 25)]);
+        let b = intervals(&[(2, 4), (9, 10), (12, 15)]);
+
+        assert_eq!(
+            a.union(&b),
+            intervals(&[(0, 4), (6, 10), (12, 15), (20, 25)])
+        );
+        assert_eq!(a.union(&b), b.union(&a));
+        assert_eq!(a.union(&Intervals::default()), a);
+    }
+
+    #[test]
+    fn intervals_intersection_test() {
+        let a = intervals(&[(0, 5), (8, 12), (15, 20)]);
+        let b = intervals(&[(3, 9), (11, 16), (19, 30)]);
+
+        assert_eq!(
+            a.intersection(&b),
+            intervals(&[(3, 5), (8, 9), (11, 12), (15, 16), (19, 20)])
+        );
+        assert_eq!(a.intersection(&b), b.intersection(&a));
+        assert!(a.intersection(&Intervals::default()).is_empty());
+    }
+
+    #[test]
+    fn intervals_difference_test() {
+        let a = intervals(&[(0, 10), (20, 30)]);
+        let b = intervals(&[(-5, 1), (3, 4), (6, 8), (9, 22), (25, 26)]);
+
+        assert_eq!(
+            a.difference(&b),
+            intervals(&[(1, 3), (4, 6), (8, 9), (22, 25), (26, 30)])
+        );
+        assert_eq!(b.difference(&a), intervals(&[(-5, 0), (10, 20)]));
+        assert!(a.difference(&a).is_empty());
+    }
+
+    #[test]
+    fn intervals_complement_test() {
+        let set: Intervals<u8> = Intervals::new(vec![Interval::new(2, 4), Interval::new(6, 8)]);
+
+        assert_eq!(set.gaps().collect::<Vec<_>>(), vec![Interval::new(4, 6)]);
+        assert_eq!(
+            set.complement(Interval::new(0, 10)),
+            Intervals::new(vec![
+                Interval::new(0, 2),
+                Interval::new(4, 6),
+                Interval::new(8, 10)
+            ])
+        );
+        assert_eq!(
+            set.complement(Interval::new(3, 7)),
+            Intervals::new(vec![Interval::new(4, 6)])
+        );
+    }
 }
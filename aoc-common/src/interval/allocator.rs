@@ -0,0 +1,330 @@
+use num::PrimInt;
+
+use super::{push_merged, Interval, Intervals};
+
+/// Hands out the leftmost free span of a given length from a set of free
+/// intervals.
+///
+/// The free spans are kept in order alongside a segment tree of their
+/// lengths, so finding and allocating space take logarithmic time in the
+/// number of spans. Reserving or releasing space that overlaps or touches
+/// existing spans updates them in place, but releasing space that is not next
+/// to any free span, or reserving space strictly inside one, adds a span and
+/// takes linear time.
+///
+/// ```
+/// use aoc_common::interval::{Allocator, Interval};
+///
+/// let mut disk = Allocator::new([Interval::new(2, 4), Interval::new(6, 9)]);
+///
+/// assert_eq!(disk.allocate(3, 10), Some(Interval::new(6, 9)));
+/// assert_eq!(disk.allocate(2, 3), None);
+/// assert_eq!(disk.allocate(1, 10), Some(Interval::new(2, 3)));
+///
+/// disk.release(Interval::new(4, 6));
+/// assert_eq!(disk.largest_free(), 3);
+/// assert_eq!(disk.find(3, 10), Some(Interval::new(3, 6)));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Allocator<T = isize> {
+    /// The free spans, sorted and disjoint. Spans that have been used up are
+    /// left in place as empty intervals until the next rebuild.
+    spans: Vec<Interval<T>>,
+    /// The number of leaves in the tree, a power of two.
+    size: usize,
+    /// The longest span under each node, with the root at 1 and the spans'
+    /// lengths as the leaves.
+    tree: Vec<usize>,
+}
+
+impl<T: PrimInt> Allocator<T> {
+    /// Creates an allocator where the given intervals are free.
+    pub fn new(free: impl IntoIterator<Item = Interval<T>>) -> Self {
+        let free: Intervals<T> = free.into_iter().collect();
+        let mut ret = Self {
+            spans: free.into_iter().collect(),
+            size: 0,
+            tree: Vec::default(),
+        };
+        ret.rebuild();
+        ret
+    }
+
+    /// The number of free values.
+    pub fn free_len(&self) -> usize {
+        self.spans.iter().map(|x| x.len()).sum()
+    }
+
+    /// The length of the longest free span.
+    pub fn largest_free(&self) -> usize {
+        self.tree[1]
+    }
+
+    /// Whether `x` is free, found by binary search.
+    pub fn is_free(&self, x: T) -> bool {
+        let index = self.spans.partition_point(|s| s.end <= x);
+        self.spans.get(index).is_some_and(|s| s.contains(x))
+    }
+
+    /// The free values, as intervals.
+    pub fn free_intervals(&self) -> Intervals<T> {
+        let mut ret = Vec::with_capacity(self.spans.len());
+        for span in self.spans.iter() {
+            push_merged(&mut ret, *span);
+        }
+        Intervals::new(ret)
+    }
+
+    /// The leftmost span of `len` free values that ends at or before
+    /// `before`.
+    pub fn find(&self, len: usize, before: T) -> Option<Interval<T>> {
+        let span = self.spans[self.find_index(len)?];
+        let end = span.start + T::from(len)?;
+
+        (end <= before).then_some(Interval::new(span.start, end))
+    }
+
+    /// Finds the leftmost span of `len` free values that ends at or before
+    /// `before`, as [`Allocator::find`], and reserves it.
+    pub fn allocate(&mut self, len: usize, before: T) -> Option<Interval<T>> {
+        let index = self.find_index(len)?;
+        let span = self.spans[index];
+        let end = span.start + T::from(len)?;
+        if end > before {
+            return None;
+        }
+
+        self.spans[index].start = end;
+        self.update(index);
+        Some(Interval::new(span.start, end))
+    }
+
+    /// Marks every value in `interval` as used, splitting a free span in two
+    /// if `interval` is strictly inside it.
+    pub fn reserve(&mut self, interval: Interval<T>) {
+        if interval.is_empty() {
+            return;
+        }
+
+        let first = self.spans.partition_point(|s| s.end <= interval.start);
+        let last = self.spans.partition_point(|s| s.start < interval.end);
+
+        for index in first..last {
+            let span = self.spans[index];
+            let left = Interval::new(span.start, interval.start.min(span.end));
+            let right = Interval::new(interval.end.max(span.start), span.end);
+
+            match (left.is_empty(), right.is_empty()) {
+                (false, false) => {
+                    // only possible for a single span, which is split in two
+                    self.spans[index] = left;
+                    self.spans.insert(index + 1, right);
+                    self.rebuild();
+                    return;
+                }
+                (false, true) => self.spans[index] = left,
+                (true, false) => self.spans[index] = right,
+                (true, true) => self.spans[index] = Interval::new(span.start, span.start),
+            }
+            self.update(index);
+        }
+    }
+
+    /// Marks every value in `interval` as free, merging it with any free
+    /// spans it overlaps or touches.
+    pub fn release(&mut self, interval: Interval<T>) {
+        if interval.is_empty() {
+            return;
+        }
+
+        // the spans that overlap or touch the interval
+        let first = self.spans.partition_point(|s| s.end < interval.start);
+        let last = self.spans.partition_point(|s| s.start <= interval.end);
+
+        if first == last {
+            self.spans.insert(first, interval);
+            self.rebuild();
+            return;
+        }
+
+        let (start, end) = self.spans[first..last]
+            .iter()
+            .filter(|s| !s.is_empty())
+            .fold((interval.start, interval.end), |(start, end), s| {
+                (start.min(s.start), end.max(s.end))
+            });
+
+        // the merged span replaces the first of them, which may be one that
+        // was used up, and the rest are left empty
+        self.spans[first] = Interval::new(start, end);
+        self.update(first);
+        for index in first + 1..last {
+            self.spans[index] = Interval::new(end, end);
+            self.update(index);
+        }
+    }
+
+    /// The index of the leftmost span with at least `len` values.
+    fn find_index(&self, len: usize) -> Option<usize> {
+        if self.tree[1] < len {
+            return None;
+        }
+
+        let mut node = 1;
+        while node < self.size {
+            node = if self.tree[2 * node] >= len {
+                2 * node
+            } else {
+                2 * node + 1
+            };
+        }
+
+        Some(node - self.size).filter(|&i| i < self.spans.len())
+    }
+
+    /// Updates the tree after the span at `index` has changed.
+    fn update(&mut self, index: usize) {
+        let mut node = self.size + index;
+        self.tree[node] = self.spans[index].len();
+
+        while node > 1 {
+            node /= 2;
+            self.tree[node] = self.tree[2 * node].max(self.tree[2 * node + 1]);
+        }
+    }
+
+    /// Drops the empty spans and rebuilds the tree from scratch.
+    fn rebuild(&mut self) {
+        self.spans.retain(|s| !s.is_empty());
+        self.size = self.spans.len().next_power_of_two();
+
+        self.tree = vec![0; 2 * self.size];
+        for (i, span) in self.spans.iter().enumerate() {
+            self.tree[self.size + i] = span.len();
+        }
+        for node in (1..self.size).rev() {
+            self.tree[node] = self.tree[2 * node].max(self.tree[2 * node + 1]);
+        }
+    }
+}
+
+impl<T: PrimInt> From<Intervals<T>> for Allocator<T> {
+    fn from(value: Intervals<T>) -> Self {
+        Self::new(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The leftmost run of `len` free values ending at or before `before`,
+    /// found by brute force.
+    fn naive_find(free: &[bool], len: usize, before: usize) -> Option<usize> {
+        (0..=free.len().saturating_sub(len))
+            .find(|&s| free[s..s + len].iter().all(|x| *x))
+            .filter(|&s| s + len <= before)
+    }
+
+    #[test]
+    fn allocator_test() {
+        let n = 97;
+        let mut allocator = Allocator::new([Interval::new(0, n as i64)]);
+        let mut model = vec![true; n];
+
+        // a simple linear congruential generator, so the test is repeatable
+        let mut seed = 12345_u64;
+        let mut next = |m: usize| {
+            seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1);
+            (seed >> 33) as usize % m
+        };
+
+        for _ in 0..2000 {
+            let start = next(n);
+            let len = next(12) + 1;
+            let end = (start + len).min(n);
+
+            match next(3) {
+                0 => {
+                    allocator.reserve(Interval::new(start as i64, end as i64));
+                    model[start..end].fill(false);
+                }
+                1 => {
+                    allocator.release(Interval::new(start as i64, end as i64));
+                    model[start..end].fill(true);
+                }
+                _ => {
+                    let expected = naive_find(&model, len, end);
+                    let found = allocator.allocate(len, end as i64);
+                    assert_eq!(found.map(|x| x.start as usize), expected);
+
+                    if let Some(s) = expected {
+                        model[s..s + len].fill(false);
+                    }
+                }
+            }
+
+            let x = next(n);
+            assert_eq!(allocator.is_free(x as i64), model[x]);
+            assert_eq!(
+                allocator.largest_free(),
+                model
+                    .split(|x| !*x)
+                    .map(|run| run.len())
+                    .max()
+                    .unwrap_or_default()
+            );
+            assert_eq!(allocator.free_len(), model.iter().filter(|x| **x).count());
+        }
+
+        let expected: Intervals<i64> = model
+            .iter()
+            .enumerate()
+            .filter(|(_, free)| **free)
+            .map(|(i, _)| Interval::new(i as i64, i as i64 + 1))
+            .collect();
+        assert_eq!(allocator.free_intervals(), expected);
+    }
+
+    #[test]
+    fn allocator_spans_test() {
+        let mut allocator: Allocator<u64> = Allocator::new([
+            Interval::new(18, 20),
+            Interval::new(10, 12),
+            Interval::new(11, 13),
+        ]);
+
+        assert_eq!(
+            allocator.free_intervals(),
+            Intervals::new(vec![Interval::new(10, 13), Interval::new(18, 20)])
+        );
+        assert!(!allocator.is_free(5));
+        assert!(!allocator.is_free(20));
+        assert_eq!(allocator.find(4, 100), None);
+        assert_eq!(allocator.allocate(3, 100), Some(Interval::new(10, 13)));
+        assert_eq!(allocator.allocate(2, 100), Some(Interval::new(18, 20)));
+        assert_eq!(allocator.largest_free(), 0);
+        assert_eq!(allocator.allocate(1, 100), None);
+
+        // releasing across the used up spans merges them
+        allocator.release(Interval::new(12, 19));
+        assert_eq!(
+            allocator.free_intervals(),
+            Intervals::new(vec![Interval::new(12, 19)])
+        );
+
+        allocator.reserve(Interval::new(14, 16));
+        assert_eq!(
+            allocator.free_intervals(),
+            Intervals::new(vec![Interval::new(12, 14), Interval::new(16, 19)])
+        );
+        assert_eq!(allocator.allocate(3, 100), Some(Interval::new(16, 19)));
+
+        let mut empty = Allocator::<i32>::new([]);
+        assert_eq!(empty.largest_free(), 0);
+        assert_eq!(empty.find(1, 10), None);
+        assert!(empty.free_intervals().is_empty());
+        empty.release(Interval::new(-3, 0));
+        assert_eq!(empty.allocate(2, 0), Some(Interval::new(-3, -1)));
+    }
+}
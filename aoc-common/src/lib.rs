@@ -4,3 +4,8 @@ pub mod geometry;
 pub mod grid;
 pub mod interval;
 pub mod pathfinding;
+pub mod pattern;
+#[cfg(feature = "record")]
+pub mod record;
+pub mod region;
+pub mod render;
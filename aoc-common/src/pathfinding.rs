@@ -1,13 +1,13 @@
 use std::{
     cmp::Ordering,
-    collections::{hash_map::Entry, BinaryHeap},
+    collections::{hash_map::Entry, BinaryHeap, VecDeque},
     fmt::Debug,
     hash::Hash,
 };
 
-use rustc_hash::FxHashMap;
+use rustc_hash::{FxHashMap, FxHashSet};
 
-type ParentsMemo<N> = FxHashMap<N, (i64, Vec<N>)>;
+type ParentsMemo<N, C = i64> = FxHashMap<N, (C, Vec<N>)>;
 
 #[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
 struct DijkstraNode<N>
@@ -58,29 +58,42 @@ where
     FS: Fn(&N) -> bool,
 {
     dijkstra_internal(start, successors, success)
-        .map(|(parents, target)| (parents[&target].0, build_paths(&parents, start, &target)))
+        .map(|(parents, target)| (parents[&target].0, build_paths(&parents, &target)))
 }
 
-fn build_paths<N>(parents: &ParentsMemo<N>, start: &N, target: &N) -> Vec<Vec<N>>
+fn build_paths<N, C>(parents: &ParentsMemo<N, C>, target: &N) -> Vec<Vec<N>>
 where
     N: Eq + Clone + Hash,
 {
-    if start == target {
-        return vec![vec![target.clone()]];
-    }
+    Paths::new(parents, std::slice::from_ref(target))
+        .map(|p| p.into_iter().cloned().collect())
+        .collect()
+}
 
-    if let Some((_, parent_nodes)) = parents.get(target) {
-        parent_nodes
-            .iter()
-            .flat_map(|x| build_paths(parents, start, x))
-            .map(|mut p| {
-                p.push(target.clone());
-                p
-            })
-            .collect()
-    } else {
-        Vec::default()
-    }
+/// Explores every node reachable from `start`, returning the cost of the
+/// cheapest path to each of them along with every predecessor on such a path.
+///
+/// Following the predecessors back from any node yields all of its cheapest
+/// paths from `start`, which has itself a cost of 0 and no predecessors.
+pub fn dijkstra_all<N, FN, IN>(start: &N, successors: FN) -> FxHashMap<N, (i64, Vec<N>)>
+where
+    N: Eq + Clone + Hash,
+    FN: Fn(&N) -> IN,
+    IN: IntoIterator<Item = (N, i64)>,
+{
+    dijkstra_search([start.clone()], successors, |_| false, false).0
+}
+
+/// Like [`dijkstra_all`], but the search begins from every one of `starts`,
+/// so each node's cost is that of the cheapest path from its nearest start.
+pub fn dijkstra_all_multi<N, FN, IN, IS>(starts: IS, successors: FN) -> FxHashMap<N, (i64, Vec<N>)>
+where
+    N: Eq + Clone + Hash,
+    FN: Fn(&N) -> IN,
+    IN: IntoIterator<Item = (N, i64)>,
+    IS: IntoIterator<Item = N>,
+{
+    dijkstra_search(starts, successors, |_| false, false).0
 }
 
 fn dijkstra_internal<N, FN, IN, FS>(
@@ -94,17 +107,53 @@ where
     IN: IntoIterator<Item = (N, i64)>,
     FS: Fn(&N) -> bool,
 {
-    let mut parents = FxHashMap::default();
+    let (parents, targets) = dijkstra_search([start.clone()], successors, success, false);
+    targets.into_iter().next().map(|target| (parents, target))
+}
+
+/// The search behind the dijkstra variants. This stops at the first node
+/// satisfying `success` unless `all_targets` is set, in which case it carries
+/// on to find every such node with the same cost.
+fn dijkstra_search<N, FN, IN, FS, IS>(
+    starts: IS,
+    successors: FN,
+    success: FS,
+    all_targets: bool,
+) -> (ParentsMemo<N>, Vec<N>)
+where
+    N: Eq + Clone + Hash,
+    FN: Fn(&N) -> IN,
+    IN: IntoIterator<Item = (N, i64)>,
+    FS: Fn(&N) -> bool,
+    IS: IntoIterator<Item = N>,
+{
+    let mut parents: ParentsMemo<N> = FxHashMap::default();
     let mut q = BinaryHeap::default();
-    q.push(DijkstraNode {
-        cost: 0,
-        node: start.clone(),
-    });
-    parents.insert(start.clone(), (0, Vec::default()));
+    for start in starts {
+        parents.insert(start.clone(), (0, Vec::default()));
+        q.push(DijkstraNode {
+            cost: 0,
+            node: start,
+        });
+    }
 
+    let mut targets: Vec<N> = Vec::default();
     while let Some(DijkstraNode { cost, node }) = q.pop() {
+        // a cheaper route to this node was found after it was queued
+        if cost > parents[&node].0 {
+            continue;
+        }
+
+        if targets.first().is_some_and(|t| cost > parents[t].0) {
+            break;
+        }
+
         if success(&node) {
-            return Some((parents, node));
+            targets.push(node);
+            if !all_targets {
+                break;
+            }
+            continue;
         }
 
         for (n, c) in successors(&node) {
@@ -139,5 +188,794 @@ where
         }
     }
 
-    None
+    (parents, targets)
+}
+
+/// Like [`dijkstra_with_paths`], but returns the cheapest paths as a
+/// [`ShortestPaths`] rather than materialising each of them. Every node
+/// satisfying `success` that can be reached for the cheapest cost is a target.
+///
+/// Edge costs must be positive for the predecessors to be complete.
+pub fn dijkstra_dag<N, FN, IN, FS>(
+    start: &N,
+    successors: FN,
+    success: FS,
+) -> Option<ShortestPaths<N>>
+where
+    N: Eq + Clone + Hash,
+    FN: Fn(&N) -> IN,
+    IN: IntoIterator<Item = (N, i64)>,
+    FS: Fn(&N) -> bool,
+{
+    let (parents, targets) = dijkstra_search([start.clone()], successors, success, true);
+    ShortestPaths::new(parents, targets)
+}
+
+/// Every cheapest path to one or more targets, stored as the DAG of each
+/// node's predecessors rather than as individual paths.
+///
+/// The number of cheapest paths can grow exponentially with the size of the
+/// graph, but the DAG cannot; paths are only produced on demand, and none of
+/// the operations here recurse.
+///
+/// ```
+/// use aoc_common::pathfinding;
+///
+/// // a diamond: 0 -> 1 -> 3 and 0 -> 2 -> 3 both cost 2, 0 -> 3 costs 5
+/// let successors = |x: &u8| match x {
+///     0 => vec![(1, 1), (2, 1), (3, 5)],
+///     1 | 2 => vec![(3, 1)],
+///     _ => vec![],
+/// };
+///
+/// let dag = pathfinding::dijkstra_dag(&0, successors, |x| *x == 3).unwrap();
+///
+/// assert_eq!(dag.cost(), 2);
+/// assert_eq!(dag.count(), 2);
+/// assert_eq!(dag.nodes().len(), 4);
+/// assert_eq!(dag.paths().next(), Some(vec![&0, &1, &3]));
+/// ```
+#[derive(Debug, Clone)]
+pub struct ShortestPaths<N>
+where
+    N: Eq + Hash,
+{
+    parents: ParentsMemo<N>,
+    targets: Vec<N>,
+    cost: i64,
+}
+
+impl<N> ShortestPaths<N>
+where
+    N: Eq + Clone + Hash,
+{
+    /// Builds the paths to the cheapest of `candidates` from the output of
+    /// [`dijkstra_all`] (or any map of cost and predecessors). Candidates that
+    /// are unreachable or more expensive than the cheapest are dropped, and
+    /// `None` is returned if none are reachable.
+    pub fn new(
+        parents: FxHashMap<N, (i64, Vec<N>)>,
+        candidates: impl IntoIterator<Item = N>,
+    ) -> Option<Self> {
+        let mut targets: Vec<N> = Vec::default();
+        let mut cost = i64::MAX;
+
+        for n in candidates {
+            let Some((c, _)) = parents.get(&n) else {
+                continue;
+            };
+
+            match c.cmp(&cost) {
+                Ordering::Less => {
+                    cost = *c;
+                    targets.clear();
+                    targets.push(n);
+                }
+                Ordering::Equal if !targets.contains(&n) => targets.push(n),
+                _ => (),
+            }
+        }
+
+        if targets.is_empty() {
+            None
+        } else {
+            Some(Self {
+                parents,
+                targets,
+                cost,
+            })
+        }
+    }
+
+    /// The cost of every one of the paths.
+    pub fn cost(&self) -> i64 {
+        self.cost
+    }
+
+    /// The nodes at the end of the paths.
+    pub fn targets(&self) -> &[N] {
+        &self.targets
+    }
+
+    /// Lazily enumerates every path, each running from a start to a target.
+    pub fn paths(&self) -> Paths<'_, N> {
+        Paths::new(&self.parents, &self.targets)
+    }
+
+    /// The set of nodes that lie on at least one of the paths.
+    pub fn nodes(&self) -> FxHashSet<&N> {
+        let mut seen = self.targets.iter().collect::<FxHashSet<_>>();
+        let mut stack = self.targets.iter().collect::<Vec<_>>();
+
+        while let Some(node) = stack.pop() {
+            for parent in self.parents[node].1.iter() {
+                if seen.insert(parent) {
+                    stack.push(parent);
+                }
+            }
+        }
+
+        seen
+    }
+
+    /// The number of distinct paths, saturating at `usize::MAX`.
+    pub fn count(&self) -> usize {
+        // as costs are positive, every node's predecessors are cheaper than it
+        let mut nodes = self.nodes().into_iter().collect::<Vec<_>>();
+        nodes.sort_by_key(|n| self.parents[*n].0);
+
+        let mut counts: FxHashMap<&N, usize> = FxHashMap::default();
+        for node in nodes {
+            let parents = &self.parents[node].1;
+            let count = if parents.is_empty() {
+                1
+            } else {
+                parents
+                    .iter()
+                    .map(|p| counts.get(p).copied().unwrap_or(0))
+                    .fold(0, usize::saturating_add)
+            };
+            counts.insert(node, count);
+        }
+
+        self.targets
+            .iter()
+            .map(|t| counts[t])
+            .fold(0, usize::saturating_add)
+    }
+}
+
+/// An iterator over the paths in a predecessor DAG, created by
+/// [`ShortestPaths::paths`].
+///
+/// Each path is found by walking back from a target along the predecessors,
+/// with an explicit stack holding the position in each node's predecessors.
+#[derive(Debug, Clone)]
+pub struct Paths<'a, N, C = i64> {
+    parents: &'a ParentsMemo<N, C>,
+    targets: std::slice::Iter<'a, N>,
+    stack: Vec<(&'a N, usize)>,
+}
+
+impl<'a, N, C> Paths<'a, N, C>
+where
+    N: Eq + Hash,
+{
+    fn new(parents: &'a ParentsMemo<N, C>, targets: &'a [N]) -> Self {
+        Self {
+            parents,
+            targets: targets.iter(),
+            stack: Vec::default(),
+        }
+    }
+}
+
+impl<'a, N, C> Iterator for Paths<'a, N, C>
+where
+    N: Eq + Hash,
+{
+    type Item = Vec<&'a N>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let Some(&(node, idx)) = self.stack.last() else {
+                let target = self.targets.next()?;
+                self.stack.push((target, 0));
+                continue;
+            };
+
+            let parents = self
+                .parents
+                .get(node)
+                .map(|(_, p)| p.as_slice())
+                .unwrap_or_default();
+
+            if parents.is_empty() {
+                // reached a start, so the stack holds a complete path
+                let path = self.stack.iter().rev().map(|(n, _)| *n).collect();
+                self.stack.pop();
+                return Some(path);
+            }
+
+            match parents.get(idx) {
+                Some(parent) => {
+                    if let Some(top) = self.stack.last_mut() {
+                        top.1 += 1;
+                    }
+                    self.stack.push((parent, 0));
+                }
+                None => {
+                    self.stack.pop();
+                }
+            }
+        }
+    }
+}
+
+/// Returns the number of steps from `start` to the nearest node satisfying
+/// `success`, where every edge has a cost of 1.
+pub fn bfs<N, FN, IN, FS>(start: &N, successors: FN, success: FS) -> Option<usize>
+where
+    N: Eq + Clone + Hash,
+    FN: Fn(&N) -> IN,
+    IN: IntoIterator<Item = N>,
+    FS: Fn(&N) -> bool,
+{
+    let (parents, target) = bfs_internal(start, successors, success);
+    target.map(|target| parents[&target].0)
+}
+
+/// Like [`bfs`], but also returns every shortest path to the target.
+pub fn bfs_with_paths<N, FN, IN, FS>(
+    start: &N,
+    successors: FN,
+    success: FS,
+) -> Option<(usize, Vec<Vec<N>>)>
+where
+    N: Eq + Clone + Hash,
+    FN: Fn(&N) -> IN,
+    IN: IntoIterator<Item = N>,
+    FS: Fn(&N) -> bool,
+{
+    let (parents, target) = bfs_internal(start, successors, success);
+    target.map(|target| (parents[&target].0, build_paths(&parents, &target)))
+}
+
+/// Returns the number of steps from `start` to every reachable node,
+/// including `start` itself.
+pub fn bfs_reach<N, FN, IN>(start: &N, successors: FN) -> FxHashMap<N, usize>
+where
+    N: Eq + Clone + Hash,
+    FN: Fn(&N) -> IN,
+    IN: IntoIterator<Item = N>,
+{
+    bfs_internal(start, successors, |_| false)
+        .0
+        .into_iter()
+        .map(|(n, (dist, _))| (n, dist))
+        .collect()
+}
+
+/// Like [`bfs_reach`], but also returns, for every reachable node, each of
+/// its predecessors on a shortest path from `start`.
+pub fn bfs_reach_with_paths<N, FN, IN>(start: &N, successors: FN) -> FxHashMap<N, (usize, Vec<N>)>
+where
+    N: Eq + Clone + Hash,
+    FN: Fn(&N) -> IN,
+    IN: IntoIterator<Item = N>,
+{
+    bfs_internal(start, successors, |_| false).0
+}
+
+fn bfs_internal<N, FN, IN, FS>(
+    start: &N,
+    successors: FN,
+    success: FS,
+) -> (ParentsMemo<N, usize>, Option<N>)
+where
+    N: Eq + Clone + Hash,
+    FN: Fn(&N) -> IN,
+    IN: IntoIterator<Item = N>,
+    FS: Fn(&N) -> bool,
+{
+    let mut parents: ParentsMemo<N, usize> = FxHashMap::default();
+    let mut q = VecDeque::default();
+    q.push_back(start.clone());
+    parents.insert(start.clone(), (0, Vec::default()));
+
+    while let Some(node) = q.pop_front() {
+        if success(&node) {
+            return (parents, Some(node));
+        }
+
+        let next_dist = parents[&node].0 + 1;
+        for n in successors(&node) {
+            match parents.entry(n.clone()) {
+                Entry::Occupied(mut e) => {
+                    let (existing_dist, parent_nodes) = e.get_mut();
+                    if *existing_dist == next_dist {
+                        parent_nodes.push(node.clone());
+                    }
+                }
+                Entry::Vacant(e) => {
+                    e.insert((next_dist, vec![node.clone()]));
+                    q.push_back(n);
+                }
+            }
+        }
+    }
+
+    (parents, None)
+}
+
+/// Returns every distinct node satisfying `success` that is reachable from
+/// `start`, in the order a depth-first search finds them.
+///
+/// Each node is visited at most once, and the successors of nodes satisfying
+/// `success` are not explored.
+pub fn dfs<N, FN, IN, FS>(start: &N, successors: FN, success: FS) -> Vec<N>
+where
+    N: Eq + Clone + Hash,
+    FN: Fn(&N) -> IN,
+    IN: IntoIterator<Item = N>,
+    FS: Fn(&N) -> bool,
+{
+    dfs_internal(start, successors, success).0
+}
+
+/// Like [`dfs`], but also returns the path the search took to reach each of
+/// the found nodes. These are not necessarily the shortest paths.
+pub fn dfs_with_paths<N, FN, IN, FS>(start: &N, successors: FN, success: FS) -> Vec<Vec<N>>
+where
+    N: Eq + Clone + Hash,
+    FN: Fn(&N) -> IN,
+    IN: IntoIterator<Item = N>,
+    FS: Fn(&N) -> bool,
+{
+    let (found, parents) = dfs_internal(start, successors, success);
+
+    found
+        .into_iter()
+        .map(|target| {
+            let mut path = vec![target];
+            while let Some(parent) = path.last().and_then(|n| parents.get(n)) {
+                path.push(parent.clone());
+            }
+            path.reverse();
+            path
+        })
+        .collect()
+}
+
+fn dfs_internal<N, FN, IN, FS>(start: &N, successors: FN, success: FS) -> (Vec<N>, FxHashMap<N, N>)
+where
+    N: Eq + Clone + Hash,
+    FN: Fn(&N) -> IN,
+    IN: IntoIterator<Item = N>,
+    FS: Fn(&N) -> bool,
+{
+    let mut found = Vec::default();
+    let mut parents = FxHashMap::default();
+    let mut visited = FxHashSet::default();
+    let mut stack = vec![start.clone()];
+    visited.insert(start.clone());
+
+    while let Some(node) = stack.pop() {
+        if success(&node) {
+            found.push(node);
+            continue;
+        }
+
+        for n in successors(&node) {
+            if visited.insert(n.clone()) {
+                parents.insert(n.clone(), node.clone());
+                stack.push(n);
+            }
+        }
+    }
+
+    (found, parents)
+}
+
+/// Returns the number of distinct paths from `start` to any node satisfying
+/// `success`. A path ends at the first node satisfying `success` on it.
+///
+/// The graph is expected to be acyclic; any edge that would close a cycle is
+/// ignored. Counts are memoized per node, so shared sub-paths are only
+/// explored once.
+pub fn count_paths<N, FN, IN, FS>(start: &N, successors: FN, success: FS) -> usize
+where
+    N: Eq + Clone + Hash,
+    FN: Fn(&N) -> IN,
+    IN: IntoIterator<Item = N>,
+    FS: Fn(&N) -> bool,
+{
+    let mut counts: FxHashMap<N, usize> = FxHashMap::default();
+    let mut in_progress = FxHashSet::default();
+    let mut stack = vec![(start.clone(), false)];
+
+    while let Some((node, expanded)) = stack.pop() {
+        if counts.contains_key(&node) {
+            continue;
+        }
+
+        if success(&node) {
+            counts.insert(node, 1);
+            continue;
+        }
+
+        if expanded {
+            let total = successors(&node)
+                .into_iter()
+                .map(|n| counts.get(&n).copied().unwrap_or(0))
+                .sum();
+            in_progress.remove(&node);
+            counts.insert(node, total);
+        } else if in_progress.insert(node.clone()) {
+            stack.push((node.clone(), true));
+            stack.extend(
+                successors(&node)
+                    .into_iter()
+                    .filter(|n| !counts.contains_key(n) && !in_progress.contains(n))
+                    .map(|n| (n, false)),
+            );
+        }
+    }
+
+    counts[start]
+}
+
+/// Like [`count_paths`], but also returns every one of the paths. As the
+/// number of paths can grow exponentially, prefer [`count_paths`] where only
+/// the count is needed.
+///
+/// Only simple paths are considered, so this terminates on cyclic graphs.
+pub fn count_paths_with_paths<N, FN, IN, FS>(
+    start: &N,
+    successors: FN,
+    success: FS,
+) -> (usize, Vec<Vec<N>>)
+where
+    N: Eq + Clone + Hash,
+    FN: Fn(&N) -> IN,
+    IN: IntoIterator<Item = N>,
+    FS: Fn(&N) -> bool,
+{
+    let mut paths = Vec::default();
+    let mut stack = vec![vec![start.clone()]];
+
+    while let Some(path) = stack.pop() {
+        let node = &path[path.len() - 1];
+
+        if success(node) {
+            paths.push(path);
+            continue;
+        }
+
+        for n in successors(node) {
+            if !path.contains(&n) {
+                let mut next = path.clone();
+                next.push(n);
+                stack.push(next);
+            }
+        }
+    }
+
+    (paths.len(), paths)
+}
+
+/// Returns the cost of the cheapest path from `start` to a node satisfying
+/// `success`, guided by `heuristic`.
+///
+/// The heuristic must never overestimate the remaining cost, and must be
+/// consistent (it may not decrease by more than an edge's cost across that
+/// edge) for the result to be optimal.
+pub fn astar<N, FN, IN, FH, FS>(
+    start: &N,
+    successors: FN,
+    heuristic: FH,
+    success: FS,
+) -> Option<i64>
+where
+    N: Eq + Clone + Hash,
+    FN: Fn(&N) -> IN,
+    IN: IntoIterator<Item = (N, i64)>,
+    FH: Fn(&N) -> i64,
+    FS: Fn(&N) -> bool,
+{
+    astar_internal(start, successors, heuristic, success)
+        .map(|(parents, target)| parents[&target].0)
+}
+
+/// Like [`astar`], but also returns every cheapest path to the target.
+pub fn astar_with_paths<N, FN, IN, FH, FS>(
+    start: &N,
+    successors: FN,
+    heuristic: FH,
+    success: FS,
+) -> Option<(i64, Vec<Vec<N>>)>
+where
+    N: Eq + Clone + Hash,
+    FN: Fn(&N) -> IN,
+    IN: IntoIterator<Item = (N, i64)>,
+    FH: Fn(&N) -> i64,
+    FS: Fn(&N) -> bool,
+{
+    astar_internal(start, successors, heuristic, success)
+        .map(|(parents, target)| (parents[&target].0, build_paths(&parents, &target)))
+}
+
+fn astar_internal<N, FN, IN, FH, FS>(
+    start: &N,
+    successors: FN,
+    heuristic: FH,
+    success: FS,
+) -> Option<(ParentsMemo<N>, N)>
+where
+    N: Eq + Clone + Hash,
+    FN: Fn(&N) -> IN,
+    IN: IntoIterator<Item = (N, i64)>,
+    FH: Fn(&N) -> i64,
+    FS: Fn(&N) -> bool,
+{
+    let mut parents: ParentsMemo<N> = FxHashMap::default();
+    let mut q = BinaryHeap::default();
+    // the heap is ordered by the estimated total cost, with the cost so far
+    // carried along with the node
+    q.push(DijkstraNode {
+        cost: heuristic(start),
+        node: (start.clone(), 0),
+    });
+    parents.insert(start.clone(), (0, Vec::default()));
+
+    let mut target: Option<(N, i64)> = None;
+    while let Some(DijkstraNode {
+        cost: estimate,
+        node: (node, cost),
+    }) = q.pop()
+    {
+        if cost > parents[&node].0 {
+            continue;
+        }
+
+        // nodes queued with the same estimate as the target may still be on
+        // a cheapest path to it, so they are expanded before stopping
+        if target.as_ref().is_some_and(|(_, best)| estimate > *best) {
+            break;
+        }
+
+        if success(&node) {
+            if target.is_none() {
+                target = Some((node, estimate));
+            }
+            continue;
+        }
+
+        for (n, c) in successors(&node) {
+            let next_cost = cost + c;
+            match parents.entry(n.clone()) {
+                Entry::Occupied(mut e) => {
+                    let (existing_cost, parent_nodes) = e.get_mut();
+                    match next_cost.cmp(existing_cost) {
+                        Ordering::Less => {
+                            parent_nodes.clear();
+                            parent_nodes.push(node.clone());
+                            *existing_cost = next_cost;
+                            q.push(DijkstraNode {
+                                cost: next_cost + heuristic(&n),
+                                node: (n, next_cost),
+                            });
+                        }
+                        Ordering::Equal => {
+                            parent_nodes.push(node.clone());
+                        }
+                        _ => (),
+                    }
+                }
+                Entry::Vacant(e) => {
+                    e.insert((next_cost, vec![node.clone()]));
+                    q.push(DijkstraNode {
+                        cost: next_cost + heuristic(&n),
+                        node: (n, next_cost),
+                    });
+                }
+            }
+        }
+    }
+
+    target.map(|(target, _)| (parents, target))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // a 4x4 grid of cells numbered row-major, with walls at 5 and 6
+    fn grid_successors(x: &i64) -> Vec<i64> {
+        let (r, c) = (x / 4, x % 4);
+        [(r - 1, c), (r + 1, c), (r, c - 1), (r, c + 1)]
+            .into_iter()
+            .filter(|&(r, c)| (0..4).contains(&r) && (0..4).contains(&c))
+            .map(|(r, c)| r * 4 + c)
+            .filter(|n| *n != 5 && *n != 6)
+            .collect()
+    }
+
+    #[test]
+    fn bfs_test() {
+        assert_eq!(bfs(&0, grid_successors, |x| *x == 15), Some(6));
+        assert_eq!(bfs(&0, grid_successors, |x| *x == 0), Some(0));
+        assert_eq!(bfs(&0, grid_successors, |x| *x == 5), None);
+
+        let (dist, paths) = bfs_with_paths(&0, grid_successors, |x| *x == 9).unwrap();
+        assert_eq!(dist, 3);
+        assert_eq!(paths, vec![vec![0, 4, 8, 9]]);
+
+        let (_, paths) = bfs_with_paths(&0, grid_successors, |x| *x == 15).unwrap();
+        assert_eq!(paths.len(), 5);
+        assert!(paths
+            .iter()
+            .all(|p| p.len() == 7 && p[0] == 0 && p[6] == 15));
+    }
+
+    #[test]
+    fn bfs_reach_test() {
+        let reach = bfs_reach(&0, grid_successors);
+        assert_eq!(reach.len(), 14);
+        assert_eq!(reach[&0], 0);
+        assert_eq!(reach[&7], 4);
+        assert!(!reach.contains_key(&5));
+
+        let reach = bfs_reach_with_paths(&0, grid_successors);
+        assert_eq!(reach[&15].0, 6);
+        assert_eq!(reach[&15].1.len(), 2);
+    }
+
+    #[test]
+    fn dfs_test() {
+        let mut found = dfs(&0, grid_successors, |x| *x == 7 || *x == 12);
+        found.sort();
+        assert_eq!(found, vec![7, 12]);
+
+        // 4 can only be reached through 2, which is not explored past
+        let chain = |x: &u32| [x + 1].into_iter().filter(|n| *n <= 5);
+        assert_eq!(dfs(&0, chain, |x| *x == 2 || *x == 4), vec![2]);
+
+        for path in dfs_with_paths(&0, grid_successors, |x| *x == 7 || *x == 12) {
+            assert_eq!(path[0], 0);
+            assert!(path
+                .windows(2)
+                .all(|w| grid_successors(&w[0]).contains(&w[1])));
+        }
+    }
+
+    #[test]
+    fn count_paths_test() {
+        // paths through a DAG where each node x leads to x + 1 and x + 2
+        let successors = |x: &u32| [x + 1, x + 2].into_iter().filter(|n| *n <= 10);
+        assert_eq!(count_paths(&0, successors, |x| *x == 10), 89);
+        assert_eq!(count_paths(&0, successors, |x| *x == 11), 0);
+
+        let (count, paths) = count_paths_with_paths(&0, successors, |x| *x == 4);
+        assert_eq!(count, 5);
+        assert!(paths.contains(&vec![0, 2, 4]));
+
+        // cycles are ignored rather than looping forever
+        assert!(count_paths(&0, grid_successors, |x| *x == 15) > 0);
+        let (count, _) = count_paths_with_paths(&0, grid_successors, |x| *x == 3);
+        assert!(count > 0);
+    }
+
+    #[test]
+    fn dijkstra_all_test() {
+        let weighted = |x: &i64| grid_successors(x).into_iter().map(|n| (n, 3));
+
+        let all = dijkstra_all(&0, weighted);
+        assert_eq!(all.len(), 14);
+        assert_eq!(all[&0], (0, vec![]));
+        assert_eq!(all[&15].0, 18);
+        assert_eq!(all[&15].1.len(), 2);
+        assert_eq!(Some(all[&15].0), dijkstra(&0, weighted, |x| *x == 15));
+
+        let multi = dijkstra_all_multi([0, 15], weighted);
+        assert_eq!(multi[&0].0, 0);
+        assert_eq!(multi[&15].0, 0);
+        assert_eq!(multi[&3].0, 9);
+        assert_eq!(multi[&12].0, 9);
+    }
+
+    #[test]
+    fn shortest_paths_test() {
+        let weighted = |x: &i64| grid_successors(x).into_iter().map(|n| (n, 1));
+
+        let dag = dijkstra_dag(&0, weighted, |x| *x == 15).unwrap();
+        assert_eq!(dag.cost(), 6);
+        assert_eq!(dag.targets(), &[15]);
+        assert_eq!(dag.count(), 5);
+        assert_eq!(dag.paths().count(), 5);
+        assert!(dag
+            .paths()
+            .all(|p| p.len() == 7 && *p[0] == 0 && *p[6] == 15));
+        assert_eq!(dag.nodes().len(), 14);
+
+        // both 3 and 12 are 3 steps away, so both are targets
+        let dag = dijkstra_dag(&0, weighted, |x| *x == 3 || *x == 12).unwrap();
+        assert_eq!(dag.targets().len(), 2);
+        assert_eq!(dag.count(), 2);
+
+        let dag = ShortestPaths::new(dijkstra_all(&0, weighted), [15, 7, 9]).unwrap();
+        assert_eq!(dag.targets(), &[9]);
+        assert_eq!(dag.paths().collect::<Vec<_>>(), vec![vec![&0, &4, &8, &9]]);
+
+        assert!(dijkstra_dag(&0, weighted, |x| *x == 5).is_none());
+    }
+
+    #[test]
+    fn shortest_paths_count_test() {
+        // a ladder of 64 diamonds has 2^64 paths, which would never finish
+        // being enumerated
+        let successors = |x: &u32| match x % 3 {
+            0 if *x < 192 => vec![(x + 1, 1), (x + 2, 1)],
+            1 => vec![(x + 2, 1)],
+            2 => vec![(x + 1, 1)],
+            _ => vec![],
+        };
+
+        let dag = dijkstra_dag(&0, successors, |x| *x == 192).unwrap();
+        assert_eq!(dag.cost(), 128);
+        assert_eq!(dag.count(), usize::MAX);
+        assert_eq!(dag.nodes().len(), 193);
+        assert_eq!(dag.paths().take(3).count(), 3);
+    }
+
+    #[test]
+    fn astar_test() {
+        let weighted = |x: &i64| grid_successors(x).into_iter().map(|n| (n, 2));
+        let heuristic = |x: &i64| 2 * ((3 - x / 4) + (3 - x % 4));
+
+        assert_eq!(astar(&0, weighted, heuristic, |x| *x == 15), Some(12));
+        assert_eq!(
+            astar(&0, weighted, heuristic, |x| *x == 15),
+            dijkstra(&0, weighted, |x| *x == 15)
+        );
+        assert_eq!(astar(&0, weighted, heuristic, |x| *x == 6), None);
+
+        let (cost, paths) = astar_with_paths(&0, weighted, heuristic, |x| *x == 15).unwrap();
+        assert_eq!(cost, 12);
+        assert_eq!(paths.len(), 5);
+    }
+
+    #[test]
+    fn astar_ties_test() {
+        // 0 -> 1 -> 9 and 0 -> 2 -> 3 -> 4 -> 9 both cost 4 and the heuristic
+        // is exact, so the target is reached through 1 while the longer route
+        // is still queued with the same estimate
+        for reversed in [false, true] {
+            let successors = |x: &u8| {
+                let mut ret = match x {
+                    0 => vec![(1, 3), (2, 1)],
+                    1 | 4 => vec![(9, 1)],
+                    2 | 3 => vec![(x + 1, 1)],
+                    _ => vec![],
+                };
+                if reversed {
+                    ret.reverse();
+                }
+                ret
+            };
+            let heuristic = |x: &u8| match x {
+                0 => 4,
+                1 | 4 => 1,
+                2 => 3,
+                3 => 2,
+                _ => 0,
+            };
+
+            let (cost, mut paths) =
+                astar_with_paths(&0, successors, heuristic, |x| *x == 9).unwrap();
+            paths.sort();
+            assert_eq!(cost, 4);
+            assert_eq!(paths, vec![vec![0, 1, 9], vec![0, 2, 3, 4, 9]]);
+        }
+    }
 }
@@ -0,0 +1,290 @@
+//! Searching a [`Grid`] for words and two-dimensional stencils.
+//!
+//! A word is a sequence of values read in a straight line in one of the
+//! [`Compass`] directions. A stencil is a small rectangle of values, some of
+//! which may be wildcards, that can be placed anywhere in the grid, optionally
+//! in any of its rotations and reflections.
+use crate::{
+    direction::Compass,
+    grid::{Coordinate, Grid},
+};
+
+/// An occurrence of a word found by [`find_sequence`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SequenceMatch {
+    /// The coordinate of the first value of the word.
+    pub start: Coordinate,
+    /// The direction the word reads in.
+    pub direction: Compass,
+}
+
+/// Finds every occurrence of `word` reading in any of the given directions.
+///
+/// A palindrome will be found once for each direction it can be read in.
+///
+/// ```
+/// use aoc_common::{direction::Compass, grid::{Coordinate, Grid}, pattern};
+///
+/// let grid: Grid<char> = "XMAS\nMM..\nA.A.\nS..S".parse().unwrap();
+/// let word = ['X', 'M', 'A', 'S'];
+///
+/// let found = pattern::find_sequence(&grid, &word, &Compass::all());
+/// assert_eq!(found.len(), 3);
+/// assert!(found.iter().all(|m| m.start == Coordinate(0, 0)));
+///
+/// assert!(pattern::find_sequence(&grid, &word, &[Compass::West]).is_empty());
+/// ```
+pub fn find_sequence<T: PartialEq>(
+    grid: &Grid<T>,
+    word: &[T],
+    directions: &[Compass],
+) -> Vec<SequenceMatch> {
+    let Some(first) = word.first() else {
+        return Vec::default();
+    };
+
+    grid.cells()
+        .filter(|(_, v)| *v == first)
+        .flat_map(|(start, _)| {
+            directions.iter().filter_map(move |&direction| {
+                let (di, dj) = direction.delta();
+                let found = grid
+                    .line(start, Coordinate(di, dj))
+                    .map(|(_, v)| v)
+                    .take(word.len())
+                    .eq(word.iter());
+
+                found.then_some(SequenceMatch { start, direction })
+            })
+        })
+        .collect()
+}
+
+/// One of the 8 ways a rectangle can be rotated and reflected.
+///
+/// The stencil is first transposed if `reflected` is set, and then rotated
+/// `quarter_turns` times clockwise. This is the same order as
+/// [`Grid::orientations`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Orientation {
+    pub quarter_turns: u8,
+    pub reflected: bool,
+}
+
+impl Orientation {
+    /// All 8 orientations, in the same order as [`Grid::orientations`].
+    pub fn all() -> [Self; 8] {
+        let mut ret = [Self::default(); 8];
+        for (i, o) in ret.iter_mut().enumerate() {
+            o.quarter_turns = (i % 4) as u8;
+            o.reflected = i >= 4;
+        }
+        ret
+    }
+
+    /// The 4 rotations, without any reflection.
+    pub fn rotations() -> [Self; 4] {
+        let [a, b, c, d, ..] = Self::all();
+        [a, b, c, d]
+    }
+
+    /// Applies this orientation to a grid.
+    pub fn apply<T: Clone>(&self, grid: &Grid<T>) -> Grid<T> {
+        let mut ret = if self.reflected {
+            grid.transpose()
+        } else {
+            grid.clone()
+        };
+
+        for _ in 0..self.quarter_turns % 4 {
+            ret = ret.rotate_cw();
+        }
+
+        ret
+    }
+}
+
+/// A rectangle of values to search a grid for, where `None` matches any value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Stencil<T> {
+    cells: Grid<Option<T>>,
+}
+
+impl<T> Stencil<T> {
+    pub fn new(cells: Grid<Option<T>>) -> Self {
+        Self { cells }
+    }
+
+    /// The number of rows in the stencil.
+    pub fn n(&self) -> usize {
+        self.cells.n
+    }
+
+    /// The number of columns in the stencil.
+    pub fn m(&self) -> usize {
+        self.cells.m
+    }
+}
+
+impl Stencil<char> {
+    /// Parses a stencil from lines of text, where `wildcard` matches any
+    /// character.
+    ///
+    /// # Panics
+    ///
+    /// If the lines are not all the same length.
+    pub fn parse(s: &str, wildcard: char) -> Self {
+        let cells = s
+            .lines()
+            .map(|l| l.chars().map(|c| (c != wildcard).then_some(c)).collect())
+            .collect::<Vec<Vec<_>>>();
+
+        Self::new(cells.into())
+    }
+}
+
+impl<T: PartialEq> Stencil<T> {
+    /// Whether the stencil matches the grid with its top-left corner at
+    /// `origin`.
+    pub fn matches_at(&self, grid: &Grid<T>, origin: Coordinate) -> bool {
+        self.cells.cells().all(|(c, expected)| match expected {
+            None => true,
+            Some(expected) => {
+                grid.get_ref(Coordinate(origin.0 + c.0, origin.1 + c.1)) == Some(expected)
+            }
+        })
+    }
+}
+
+/// A placement of a stencil found by [`find_stencil`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct StencilMatch {
+    /// The coordinate of the top-left corner of the oriented stencil.
+    pub origin: Coordinate,
+    pub orientation: Orientation,
+}
+
+/// Finds every placement of `stencil`, in any of the given orientations, that
+/// fits inside the grid and matches it.
+///
+/// Orientations that produce the same stencil as an earlier one in
+/// `orientations` are skipped, so a symmetric stencil is only matched once at
+/// each position.
+///
+/// ```
+/// use aoc_common::{grid::Grid, pattern::{self, Orientation, Stencil}};
+///
+/// let grid: Grid<char> = "M.S\n.A.\nM.S\nSSM".parse().unwrap();
+/// let x_mas = Stencil::parse("M.M\n.A.\nS.S", '.');
+///
+/// let found = pattern::find_stencil(&grid, &x_mas, &Orientation::all());
+/// assert_eq!(found.len(), 1);
+/// assert_eq!(found[0].orientation.quarter_turns, 3);
+/// ```
+pub fn find_stencil<T>(
+    grid: &Grid<T>,
+    stencil: &Stencil<T>,
+    orientations: &[Orientation],
+) -> Vec<StencilMatch>
+where
+    T: Clone + PartialEq,
+{
+    let mut oriented: Vec<(Orientation, Stencil<T>)> = Vec::with_capacity(orientations.len());
+    for &orientation in orientations {
+        let s = Stencil::new(orientation.apply(&stencil.cells));
+        if !oriented.iter().any(|(_, x)| *x == s) {
+            oriented.push((orientation, s));
+        }
+    }
+
+    let mut ret = Vec::default();
+    for (orientation, s) in oriented.iter() {
+        if s.n() > grid.n || s.m() > grid.m {
+            continue;
+        }
+
+        for i in 0..=grid.n - s.n() {
+            for j in 0..=grid.m - s.m() {
+                let origin = Coordinate(i as isize, j as isize);
+                if s.matches_at(grid, origin) {
+                    ret.push(StencilMatch {
+                        origin,
+                        orientation: *orientation,
+                    });
+                }
+            }
+        }
+    }
+
+    ret
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn orientation_test() {
+        let grid: Grid<char> = "abc\ndef".parse().unwrap();
+        let orientations = grid.orientations();
+
+        for (i, o) in Orientation::all().iter().enumerate() {
+            assert_eq!(o.apply(&grid), orientations[i]);
+        }
+    }
+
+    #[test]
+    fn find_sequence_test() {
+        let grid: Grid<char> = "SAS\nAAA\nSAS".parse().unwrap();
+
+        // each edge and diagonal reads the same in both directions
+        let found = find_sequence(&grid, &['S', 'A', 'S'], &Compass::all());
+        assert_eq!(found.len(), 12);
+
+        let found = find_sequence(&grid, &['A', 'A'], &[Compass::East]);
+        assert_eq!(
+            found,
+            vec![
+                SequenceMatch {
+                    start: Coordinate(1, 0),
+                    direction: Compass::East
+                },
+                SequenceMatch {
+                    start: Coordinate(1, 1),
+                    direction: Compass::East
+                },
+            ]
+        );
+        assert!(find_sequence(&grid, &[], &Compass::all()).is_empty());
+    }
+
+    #[test]
+    fn find_stencil_test() {
+        let grid: Grid<u8> = vec![vec![1, 2, 0], vec![0, 3, 0], vec![0, 0, 0]].into();
+        let stencil = Stencil::new(vec![vec![Some(1), Some(2)], vec![None, Some(3)]].into());
+
+        let found = find_stencil(&grid, &stencil, &[Orientation::default()]);
+        assert_eq!(
+            found,
+            vec![StencilMatch {
+                origin: Coordinate(0, 0),
+                orientation: Orientation::default()
+            }]
+        );
+
+        // a 180 degree turn places the 1 in the bottom right
+        let turned = Orientation {
+            quarter_turns: 2,
+            reflected: false,
+        };
+        assert!(find_stencil(&grid, &stencil, &[turned]).is_empty());
+
+        let big = Stencil::new(Grid::new(4, 1, None));
+        assert!(find_stencil(&grid, &big, &Orientation::all()).is_empty());
+
+        // an all-wildcard stencil is symmetric, so is only matched once per
+        // placement
+        let blank = Stencil::new(Grid::new(2, 2, None));
+        assert_eq!(find_stencil(&grid, &blank, &Orientation::all()).len(), 4);
+    }
+}
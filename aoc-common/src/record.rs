@@ -0,0 +1,593 @@
+//! Recording the steps of a grid simulation as an animation.
+//!
+//! A simulation pushes snapshots of its grid, or just the cells that changed
+//! since the last snapshot, into a [`Recorder`]. The recorders here draw each
+//! frame with a [`Renderer`] and write it as a frame of an animated GIF
+//! ([`GifRecorder`]), as a numbered image file ([`FrameRecorder`]), or to the
+//! terminal ([`TerminalPlayback`]).
+//!
+//! Simulations that keep a recorder as part of their state can hold a
+//! [`Recording`], which is cheap to clone, does nothing until a recorder is
+//! attached, and keeps hold of the first error so that the simulation itself
+//! doesn't need to handle them.
+//!
+//! This module is only available with the `record` feature.
+use std::{
+    fmt,
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::PathBuf,
+    sync::{Arc, Mutex},
+    thread,
+    time::Duration,
+};
+
+use rustc_hash::FxHashMap;
+
+use crate::{
+    grid::{Coordinate, Grid},
+    render::{Renderer, Rgb},
+};
+
+/// Something that can record the frames of a grid simulation.
+pub trait Recorder<T> {
+    /// Records a full snapshot of the grid.
+    fn frame(&mut self, grid: &Grid<T>) -> io::Result<()>;
+
+    /// Records a frame that is the same as the previous one except for the
+    /// given cells.
+    ///
+    /// Returns an error if there is no previous frame.
+    fn diff(&mut self, changes: &[(Coordinate, T)]) -> io::Result<()>;
+
+    /// Writes out anything that is still buffered. No frames should be
+    /// recorded after this is called.
+    fn finish(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// The most recent frame pushed into a recorder, and which frames should be
+/// drawn.
+struct Timeline<T> {
+    current: Option<Grid<T>>,
+    every: usize,
+    pushed: usize,
+    pending: bool,
+}
+
+impl<T: Clone> Timeline<T> {
+    fn new() -> Self {
+        Self {
+            current: None,
+            every: 1,
+            pushed: 0,
+            pending: false,
+        }
+    }
+
+    /// Records a snapshot, returning whether it should be drawn.
+    fn frame(&mut self, grid: &Grid<T>) -> bool {
+        self.current = Some(grid.clone());
+        self.advance()
+    }
+
+    /// Applies a diff to the current frame, returning whether the result
+    /// should be drawn.
+    fn diff(&mut self, changes: &[(Coordinate, T)]) -> io::Result<bool> {
+        let current = self.current.as_mut().ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "a diff was recorded before the first frame",
+            )
+        })?;
+
+        for (coord, value) in changes.iter() {
+            if let Some(cell) = current.get_mut(*coord) {
+                *cell = value.clone();
+            }
+        }
+
+        Ok(self.advance())
+    }
+
+    fn advance(&mut self) -> bool {
+        let draw = self.pushed.is_multiple_of(self.every);
+        self.pushed += 1;
+        self.pending = !draw;
+        draw
+    }
+
+    /// Whether the last frame was skipped, in which case it should be drawn
+    /// so that an animation always ends on the final state.
+    fn last_skipped(&mut self) -> bool {
+        std::mem::take(&mut self.pending)
+    }
+}
+
+/// Implements [`Recorder`] for a recorder with a `timeline` field, and
+/// `draw(&mut self, &Grid<T>)` and `close(&mut self)` methods, so that only
+/// the drawing differs between them.
+macro_rules! impl_recorder {
+    ($name:ident) => {
+        impl<T, F> $name<T, F>
+        where
+            T: Clone,
+            F: Fn(&T) -> Rgb,
+        {
+            fn draw_current(&mut self) -> io::Result<()> {
+                let Some(grid) = self.timeline.current.take() else {
+                    return Ok(());
+                };
+
+                let ret = self.draw(&grid);
+                self.timeline.current = Some(grid);
+                ret
+            }
+        }
+
+        impl<T, F> Recorder<T> for $name<T, F>
+        where
+            T: Clone,
+            F: Fn(&T) -> Rgb,
+        {
+            fn frame(&mut self, grid: &Grid<T>) -> io::Result<()> {
+                if self.timeline.frame(grid) {
+                    self.draw_current()?;
+                }
+                Ok(())
+            }
+
+            fn diff(&mut self, changes: &[(Coordinate, T)]) -> io::Result<()> {
+                if self.timeline.diff(changes)? {
+                    self.draw_current()?;
+                }
+                Ok(())
+            }
+
+            fn finish(&mut self) -> io::Result<()> {
+                if self.timeline.last_skipped() {
+                    self.draw_current()?;
+                }
+                self.close()
+            }
+        }
+    };
+}
+
+/// Writes every frame into an animated GIF that loops forever.
+///
+/// The frames must all be the same size as the first one.
+pub struct GifRecorder<T, F> {
+    path: PathBuf,
+    colour: F,
+    scale: usize,
+    delay: Duration,
+    encoder: Option<gif::Encoder<BufWriter<File>>>,
+    timeline: Timeline<T>,
+}
+
+impl<T: Clone, F: Fn(&T) -> Rgb> GifRecorder<T, F> {
+    /// Creates a recorder that will write to `path` once the first frame is
+    /// recorded.
+    pub fn new(path: impl Into<PathBuf>, colour: F) -> Self {
+        Self {
+            path: path.into(),
+            colour,
+            scale: 1,
+            delay: Duration::from_millis(50),
+            encoder: None,
+            timeline: Timeline::new(),
+        }
+    }
+
+    /// Draws each cell as a `scale` by `scale` square of pixels.
+    pub fn scale(mut self, scale: usize) -> Self {
+        self.scale = scale.max(1);
+        self
+    }
+
+    /// How long each frame is shown for. GIFs only support delays in
+    /// hundredths of a second.
+    pub fn delay(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    /// Only draws every `every`th frame, along with the final one.
+    pub fn every(mut self, every: usize) -> Self {
+        self.timeline.every = every.max(1);
+        self
+    }
+
+    fn draw(&mut self, grid: &Grid<T>) -> io::Result<()> {
+        let renderer = Renderer::new(grid, &self.colour).scale(self.scale);
+        let (width, height) = renderer.dimensions();
+        let too_big = || {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("{}x{} is too large for a gif", width, height),
+            )
+        };
+        let (width, height) = (
+            u16::try_from(width).map_err(|_| too_big())?,
+            u16::try_from(height).map_err(|_| too_big())?,
+        );
+
+        let mut frame = palette_frame(width, height, &renderer.pixels());
+        frame.delay = (self.delay.as_millis() / 10).min(u16::MAX as u128) as u16;
+
+        let encoder = match self.encoder {
+            Some(ref mut e) => e,
+            None => {
+                let out = BufWriter::new(File::create(&self.path)?);
+                let mut e = gif::Encoder::new(out, width, height, &[]).map_err(io::Error::other)?;
+                e.set_repeat(gif::Repeat::Infinite)
+                    .map_err(io::Error::other)?;
+                self.encoder.insert(e)
+            }
+        };
+
+        encoder.write_frame(&frame).map_err(io::Error::other)
+    }
+
+    fn close(&mut self) -> io::Result<()> {
+        match self.encoder.take() {
+            Some(e) => e.into_inner()?.flush(),
+            None => Ok(()),
+        }
+    }
+}
+
+impl_recorder!(GifRecorder);
+
+/// Builds a frame with an exact palette when there are at most 256 colours,
+/// which is almost always the case for a grid, and falls back to quantizing
+/// otherwise.
+fn palette_frame(width: u16, height: u16, pixels: &[u8]) -> gif::Frame<'static> {
+    let mut palette: FxHashMap<[u8; 3], u8> = FxHashMap::default();
+    let mut indices = Vec::with_capacity(pixels.len() / 3);
+
+    for rgb in pixels.chunks_exact(3) {
+        let rgb = [rgb[0], rgb[1], rgb[2]];
+        let next = palette.len();
+        let idx = match palette.get(&rgb) {
+            Some(idx) => *idx,
+            None if next < 256 => {
+                palette.insert(rgb, next as u8);
+                next as u8
+            }
+            None => return gif::Frame::from_rgb_speed(width, height, pixels, 10),
+        };
+        indices.push(idx);
+    }
+
+    let mut colours = vec![0; palette.len() * 3];
+    for (rgb, idx) in palette {
+        colours[idx as usize * 3..idx as usize * 3 + 3].copy_from_slice(&rgb);
+    }
+
+    gif::Frame::from_palette_pixels(width, height, indices, colours, None)
+}
+
+/// Writes every frame as a numbered PNG or PPM file in a directory, for
+/// stitching together with another tool.
+pub struct FrameRecorder<T, F> {
+    dir: PathBuf,
+    extension: &'static str,
+    colour: F,
+    scale: usize,
+    index: usize,
+    timeline: Timeline<T>,
+}
+
+impl<T: Clone, F: Fn(&T) -> Rgb> FrameRecorder<T, F> {
+    /// Creates a recorder that writes `frame-00000.png`, `frame-00001.png`
+    /// and so on into `dir`, creating it if needed.
+    pub fn png(dir: impl Into<PathBuf>, colour: F) -> Self {
+        Self::new(dir.into(), "png", colour)
+    }
+
+    /// Like [`FrameRecorder::png`], but writes binary PPM files, which are
+    /// quicker to write.
+    pub fn ppm(dir: impl Into<PathBuf>, colour: F) -> Self {
+        Self::new(dir.into(), "ppm", colour)
+    }
+
+    fn new(dir: PathBuf, extension: &'static str, colour: F) -> Self {
+        Self {
+            dir,
+            extension,
+            colour,
+            scale: 1,
+            index: 0,
+            timeline: Timeline::new(),
+        }
+    }
+
+    /// Draws each cell as a `scale` by `scale` square of pixels.
+    pub fn scale(mut self, scale: usize) -> Self {
+        self.scale = scale.max(1);
+        self
+    }
+
+    /// Only draws every `every`th frame, along with the final one.
+    pub fn every(mut self, every: usize) -> Self {
+        self.timeline.every = every.max(1);
+        self
+    }
+
+    fn draw(&mut self, grid: &Grid<T>) -> io::Result<()> {
+        if self.index == 0 {
+            std::fs::create_dir_all(&self.dir)?;
+        }
+
+        let path = self
+            .dir
+            .join(format!("frame-{:05}.{}", self.index, self.extension));
+        self.index += 1;
+
+        Renderer::new(grid, &self.colour)
+            .scale(self.scale)
+            .save(path)
+    }
+
+    fn close(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl_recorder!(FrameRecorder);
+
+/// Plays the frames back in the terminal as they are recorded, redrawing in
+/// place with ANSI true-colour blocks.
+pub struct TerminalPlayback<T, F> {
+    colour: F,
+    delay: Duration,
+    started: bool,
+    timeline: Timeline<T>,
+}
+
+impl<T: Clone, F: Fn(&T) -> Rgb> TerminalPlayback<T, F> {
+    pub fn new(colour: F) -> Self {
+        Self {
+            colour,
+            delay: Duration::from_millis(50),
+            started: false,
+            timeline: Timeline::new(),
+        }
+    }
+
+    /// How long to pause after drawing each frame.
+    pub fn delay(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    /// Only draws every `every`th frame, along with the final one.
+    pub fn every(mut self, every: usize) -> Self {
+        self.timeline.every = every.max(1);
+        self
+    }
+
+    fn draw(&mut self, grid: &Grid<T>) -> io::Result<()> {
+        let mut out = io::stdout().lock();
+
+        // clear the screen once, then move the cursor home for each frame
+        if !std::mem::replace(&mut self.started, true) {
+            write!(out, "\x1b[2J")?;
+        }
+        write!(out, "\x1b[H{}", Renderer::new(grid, &self.colour).to_ansi())?;
+        out.flush()?;
+
+        thread::sleep(self.delay);
+        Ok(())
+    }
+
+    fn close(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl_recorder!(TerminalPlayback);
+
+struct Shared<T> {
+    recorder: Box<dyn Recorder<T> + Send>,
+    error: Option<io::Error>,
+}
+
+/// A shared handle to an optional [`Recorder`], for simulations to keep in
+/// their state.
+///
+/// Cloning the handle shares the recorder. Errors from the recorder are kept
+/// until [`Recording::finish`] is called, and any frames recorded after an
+/// error are dropped.
+///
+/// ```
+/// use aoc_common::{grid::{Coordinate, Grid}, record::Recording};
+///
+/// // a recording without a recorder does nothing
+/// let recording = Recording::default();
+/// recording.frame(&Grid::new(2, 2, '.'));
+/// recording.diff(&[(Coordinate(0, 0), '#')]);
+///
+/// assert!(!recording.is_active());
+/// assert!(recording.finish().is_ok());
+/// ```
+pub struct Recording<T> {
+    shared: Option<Arc<Mutex<Shared<T>>>>,
+}
+
+impl<T> Recording<T> {
+    pub fn new(recorder: impl Recorder<T> + Send + 'static) -> Self {
+        Self {
+            shared: Some(Arc::new(Mutex::new(Shared {
+                recorder: Box::new(recorder),
+                error: None,
+            }))),
+        }
+    }
+
+    /// Whether there is a recorder attached.
+    pub fn is_active(&self) -> bool {
+        self.shared.is_some()
+    }
+
+    fn with(&self, f: impl FnOnce(&mut dyn Recorder<T>) -> io::Result<()>) {
+        let Some(ref shared) = self.shared else {
+            return;
+        };
+
+        let mut shared = shared.lock().unwrap_or_else(|e| e.into_inner());
+        if shared.error.is_none() {
+            if let Err(e) = f(shared.recorder.as_mut()) {
+                shared.error = Some(e);
+            }
+        }
+    }
+
+    /// See [`Recorder::frame`].
+    pub fn frame(&self, grid: &Grid<T>) {
+        self.with(|r| r.frame(grid));
+    }
+
+    /// See [`Recorder::diff`].
+    pub fn diff(&self, changes: &[(Coordinate, T)]) {
+        self.with(|r| r.diff(changes));
+    }
+
+    /// Finishes the recording, returning the first error that happened while
+    /// recording, if any.
+    pub fn finish(&self) -> io::Result<()> {
+        self.with(|r| r.finish());
+
+        match self.shared {
+            Some(ref shared) => match shared
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .error
+                .take()
+            {
+                Some(e) => Err(e),
+                None => Ok(()),
+            },
+            None => Ok(()),
+        }
+    }
+}
+
+impl<T> Default for Recording<T> {
+    fn default() -> Self {
+        Self { shared: None }
+    }
+}
+
+impl<T> Clone for Recording<T> {
+    fn clone(&self) -> Self {
+        Self {
+            shared: self.shared.clone(),
+        }
+    }
+}
+
+impl<T> fmt::Debug for Recording<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Recording")
+            .field("active", &self.is_active())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn colour(c: &char) -> Rgb {
+        match c {
+            '#' => Rgb::WHITE,
+            '@' => Rgb(255, 0, 0),
+            _ => Rgb::BLACK,
+        }
+    }
+
+    #[test]
+    fn timeline_test() {
+        let mut timeline = Timeline::new();
+        timeline.every = 2;
+
+        let grid: Grid<char> = "..\n..".parse().unwrap();
+        assert!(timeline.frame(&grid));
+        assert!(!timeline.diff(&[(Coordinate(0, 0), '#')]).unwrap());
+        assert!(timeline.last_skipped());
+        assert!(!timeline.last_skipped());
+
+        assert!(timeline
+            .diff(&[(Coordinate(1, 1), '@'), (Coordinate(5, 5), '@')])
+            .unwrap());
+        assert_eq!(timeline.current.unwrap().to_string(), "#.\n.@\n");
+    }
+
+    #[test]
+    fn diff_before_frame_test() {
+        let mut timeline: Timeline<char> = Timeline::new();
+        assert!(timeline.diff(&[(Coordinate(0, 0), '#')]).is_err());
+
+        let recording = Recording::new(TerminalPlayback::new(colour));
+        recording.diff(&[(Coordinate(0, 0), '#')]);
+        recording.frame(&Grid::new(1, 1, '.'));
+        assert!(recording.finish().is_err());
+    }
+
+    #[test]
+    fn gif_test() {
+        let path = std::env::temp_dir().join(format!("aoc-record-{}.gif", std::process::id()));
+        let recording = Recording::new(GifRecorder::new(&path, colour).scale(2));
+
+        let mut grid: Grid<char> = "#..\n...".parse().unwrap();
+        recording.frame(&grid);
+        for j in 0..3 {
+            recording.diff(&[(Coordinate(1, j), '@')]);
+        }
+        grid[Coordinate(0, 2)] = '#';
+        recording.frame(&grid);
+        recording.finish().unwrap();
+
+        let mut options = gif::DecodeOptions::new();
+        options.set_color_output(gif::ColorOutput::RGBA);
+        let mut decoder = options.read_info(File::open(&path).unwrap()).unwrap();
+        assert_eq!((decoder.width(), decoder.height()), (6, 4));
+
+        let mut frames = 0;
+        while decoder.read_next_frame().unwrap().is_some() {
+            frames += 1;
+        }
+        assert_eq!(frames, 5);
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn frames_test() {
+        let dir = std::env::temp_dir().join(format!("aoc-record-{}", std::process::id()));
+        let recording = Recording::new(FrameRecorder::ppm(&dir, colour).every(2));
+
+        recording.frame(&Grid::new(2, 2, '.'));
+        for i in 0..4 {
+            recording.diff(&[(Coordinate(i / 2, i % 2), '#')]);
+        }
+        recording.finish().unwrap();
+
+        // frames 0, 2 and 4 are drawn
+        let mut files = std::fs::read_dir(&dir)
+            .unwrap()
+            .map(|e| e.unwrap().file_name().into_string().unwrap())
+            .collect::<Vec<_>>();
+        files.sort();
+        assert_eq!(
+            files,
+            vec!["frame-00000.ppm", "frame-00001.ppm", "frame-00002.ppm"]
+        );
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,279 @@
+//! Connected-component labelling of a [`Grid`].
+//!
+//! Cells are grouped into regions of 4-connected cells, where two neighbouring
+//! cells belong to the same region if their values are equal, or if a given
+//! predicate says they are connected. The flood fill is iterative, so it is
+//! safe to use on large grids.
+use crate::grid::{BoundingBox, Coordinate, Grid};
+
+/// A single 4-connected region of a grid.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Region {
+    label: usize,
+    cells: Vec<Coordinate>,
+    perimeter: usize,
+    corners: usize,
+    bounding_box: BoundingBox,
+}
+
+impl Region {
+    /// The label of this region, which is its index in [`Regions::regions`].
+    pub fn label(&self) -> usize {
+        self.label
+    }
+
+    /// The cells in the region, in the order they were filled. The first cell
+    /// is the first cell of the region in row-major order.
+    pub fn cells(&self) -> &[Coordinate] {
+        &self.cells
+    }
+
+    /// The number of cells in the region.
+    pub fn area(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// The number of cell edges that separate the region from cells outside
+    /// it, including the edges of the grid and the edges of any holes.
+    pub fn perimeter(&self) -> usize {
+        self.perimeter
+    }
+
+    /// The number of corners on the outline of the region, counting both
+    /// convex and concave corners and the corners of any holes.
+    pub fn corners(&self) -> usize {
+        self.corners
+    }
+
+    /// The number of straight sides on the outline of the region. A closed
+    /// outline has as many sides as it has corners.
+    pub fn sides(&self) -> usize {
+        self.corners
+    }
+
+    pub fn bounding_box(&self) -> BoundingBox {
+        self.bounding_box
+    }
+}
+
+/// Every region of a grid, along with the label of each cell.
+///
+/// ```
+/// use aoc_common::{grid::{Coordinate, Grid}, region::Regions};
+///
+/// let grid: Grid<char> = "AAAA\nBBCD\nBBCC\nEEEC".parse().unwrap();
+/// let regions = Regions::new(&grid);
+///
+/// assert_eq!(regions.len(), 5);
+///
+/// let c = regions.region_at(Coordinate(1, 2)).unwrap();
+/// assert_eq!((c.area(), c.perimeter(), c.sides()), (4, 10, 8));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Regions {
+    labels: Grid<Option<usize>>,
+    regions: Vec<Region>,
+}
+
+impl Regions {
+    /// Splits the grid into regions of equal, 4-connected cells.
+    pub fn new<T: PartialEq>(grid: &Grid<T>) -> Self {
+        Self::by(grid, |a, b| a == b)
+    }
+
+    /// Splits the grid into regions of 4-connected cells, where neighbouring
+    /// cells are connected if `connected` returns true for their values.
+    ///
+    /// `connected` should be symmetric; the region a cell ends up in is
+    /// otherwise dependent on the order the grid is filled.
+    pub fn by<T, F>(grid: &Grid<T>, connected: F) -> Self
+    where
+        F: Fn(&T, &T) -> bool,
+    {
+        Self::by_where(grid, connected, |_| true)
+    }
+
+    /// Like [`Regions::by`], but only cells for which `include` returns true
+    /// are labelled. Every other cell is left without a region.
+    ///
+    /// ```
+    /// use aoc_common::{grid::{Coordinate, Grid}, region::Regions};
+    ///
+    /// let grid: Grid<char> = "#.#\n#.#\n###".parse().unwrap();
+    /// let walls = Regions::by_where(&grid, |_, _| true, |c| *c == '#');
+    ///
+    /// assert_eq!(walls.len(), 1);
+    /// assert_eq!(walls.label(Coordinate(0, 1)), None);
+    /// assert_eq!(walls.regions()[0].area(), 7);
+    /// ```
+    pub fn by_where<T, F, I>(grid: &Grid<T>, connected: F, include: I) -> Self
+    where
+        F: Fn(&T, &T) -> bool,
+        I: Fn(&T) -> bool,
+    {
+        let mut labels = Grid::new(grid.n, grid.m, None);
+        let mut regions = Vec::default();
+        let mut stack = Vec::default();
+
+        for (start, value) in grid.cells() {
+            if labels[start].is_some() || !include(value) {
+                continue;
+            }
+
+            let label = regions.len();
+            let mut cells = Vec::default();
+            labels[start] = Some(label);
+            stack.push(start);
+
+            while let Some(cur) = stack.pop() {
+                cells.push(cur);
+
+                for (next, v) in grid.cardinal_neighbours(cur) {
+                    if labels[next].is_none() && include(v) && connected(&grid[cur], v) {
+                        labels[next] = Some(label);
+                        stack.push(next);
+                    }
+                }
+            }
+
+            regions.push(Self::measure(&labels, label, cells));
+        }
+
+        Self { labels, regions }
+    }
+
+    fn measure(labels: &Grid<Option<usize>>, label: usize, cells: Vec<Coordinate>) -> Region {
+        let inside = |c: Coordinate| labels.get(c).flatten() == Some(label);
+
+        let mut perimeter = 0;
+        let mut corners = 0;
+        for &cell in cells.iter() {
+            perimeter += cell
+                .cardinal_neighbours()
+                .into_iter()
+                .filter(|n| !inside(*n))
+                .count();
+
+            // each pair of adjacent sides, along with the diagonal between them
+            for (a, b, d) in [
+                (cell.north(), cell.east(), cell.northeast()),
+                (cell.east(), cell.south(), cell.southeast()),
+                (cell.south(), cell.west(), cell.southwest()),
+                (cell.west(), cell.north(), cell.northwest()),
+            ] {
+                match (inside(a), inside(b)) {
+                    (false, false) => corners += 1,
+                    (true, true) if !inside(d) => corners += 1,
+                    _ => {}
+                }
+            }
+        }
+
+        Region {
+            label,
+            bounding_box: BoundingBox::from_coords(cells.iter().copied())
+                .expect("a region always has at least one cell"),
+            cells,
+            perimeter,
+            corners,
+        }
+    }
+
+    /// The regions, ordered by the first cell of each in row-major order.
+    pub fn regions(&self) -> &[Region] {
+        &self.regions
+    }
+
+    pub fn len(&self) -> usize {
+        self.regions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.regions.is_empty()
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, Region> {
+        self.regions.iter()
+    }
+
+    /// The label of the region containing `coord`, if it is in bounds and
+    /// was included.
+    pub fn label(&self, coord: Coordinate) -> Option<usize> {
+        self.labels.get(coord).flatten()
+    }
+
+    /// The region containing `coord`, if it is in bounds and was included.
+    pub fn region_at(&self, coord: Coordinate) -> Option<&Region> {
+        self.label(coord).map(|l| &self.regions[l])
+    }
+
+    /// The label of every cell in the grid.
+    pub fn labels(&self) -> &Grid<Option<usize>> {
+        &self.labels
+    }
+}
+
+impl<'a> IntoIterator for &'a Regions {
+    type Item = &'a Region;
+    type IntoIter = std::slice::Iter<'a, Region>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.regions.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn regions_test() {
+        let grid: Grid<char> = "OOOOO\nOXOXO\nOOOOO\nOXOXO\nOOOOO".parse().unwrap();
+        let regions = Regions::new(&grid);
+
+        assert_eq!(regions.len(), 5);
+
+        let outer = &regions.regions()[0];
+        assert_eq!(outer.label(), 0);
+        assert_eq!(
+            (outer.area(), outer.perimeter(), outer.sides()),
+            (21, 36, 20)
+        );
+        assert_eq!(
+            outer.bounding_box(),
+            BoundingBox {
+                min: Coordinate(0, 0),
+                max: Coordinate(4, 4)
+            }
+        );
+
+        for x in regions.iter().skip(1) {
+            assert_eq!((x.area(), x.perimeter(), x.sides()), (1, 4, 4));
+        }
+        assert_eq!(regions.label(Coordinate(3, 3)), Some(4));
+        assert_eq!(regions.label(Coordinate(5, 0)), None);
+    }
+
+    #[test]
+    fn concave_sides_test() {
+        let grid: Grid<char> = "AAAAAA\nAAABBA\nAAABBA\nABBAAA\nABBAAA\nAAAAAA"
+            .parse()
+            .unwrap();
+        let regions = Regions::new(&grid);
+
+        let a = regions.region_at(Coordinate(0, 0)).unwrap();
+        assert_eq!((a.area(), a.sides()), (28, 12));
+        assert_eq!(regions.iter().filter(|r| r.sides() == 4).count(), 2);
+    }
+
+    #[test]
+    fn large_region_test() {
+        // would overflow the stack with a recursive fill
+        let grid = Grid::new(1000, 1000, 0);
+        let regions = Regions::new(&grid);
+
+        assert_eq!(regions.len(), 1);
+        assert_eq!(regions.regions()[0].area(), 1_000_000);
+        assert_eq!(regions.regions()[0].sides(), 4);
+    }
+}
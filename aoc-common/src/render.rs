@@ -0,0 +1,279 @@
+//! Rendering a [`Grid`] as an image or as coloured blocks in a terminal.
+//!
+//! Every cell is mapped to a colour by a closure, and sets of coordinates can
+//! be highlighted on top, for example to show a path found by
+//! [`dijkstra_with_paths`](crate::pathfinding::dijkstra_with_paths).
+//!
+//! ```no_run
+//! use aoc_common::{grid::{Coordinate, Grid}, render::{Renderer, Rgb}};
+//!
+//! let grid: Grid<char> = "#..\n.#.\n..#".parse().unwrap();
+//! let renderer = Renderer::new(&grid, |c| if *c == '#' { Rgb::WHITE } else { Rgb::BLACK })
+//!     .scale(8)
+//!     .highlight([Coordinate(0, 1), Coordinate(0, 2)], Rgb(255, 0, 0));
+//!
+//! renderer.save("grid.ppm").unwrap();
+//! print!("{}", renderer.to_ansi());
+//! ```
+use std::{
+    fmt::Write as _,
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::Path,
+};
+
+use rustc_hash::FxHashMap;
+
+use crate::grid::{Coordinate, Grid};
+
+/// A 24-bit colour.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl Rgb {
+    pub const BLACK: Self = Self(0, 0, 0);
+    pub const WHITE: Self = Self(255, 255, 255);
+
+    /// Parses a `#rrggbb` hex colour.
+    ///
+    /// ```
+    /// use aoc_common::render::Rgb;
+    ///
+    /// assert_eq!(Rgb::from_hex("#ff8000"), Some(Rgb(255, 128, 0)));
+    /// assert_eq!(Rgb::from_hex("ff8000"), None);
+    /// ```
+    pub fn from_hex(hex: &str) -> Option<Self> {
+        let hex = hex.strip_prefix('#')?;
+        if hex.len() != 6 {
+            return None;
+        }
+
+        let channel = |i: usize| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok();
+        Some(Self(channel(0)?, channel(2)?, channel(4)?))
+    }
+}
+
+/// Draws a grid, with one colour per cell.
+pub struct Renderer<'a, T, F> {
+    grid: &'a Grid<T>,
+    colour: F,
+    scale: usize,
+    highlights: FxHashMap<Coordinate, Rgb>,
+}
+
+impl<'a, T, F> Renderer<'a, T, F>
+where
+    F: Fn(&T) -> Rgb,
+{
+    pub fn new(grid: &'a Grid<T>, colour: F) -> Self {
+        Self {
+            grid,
+            colour,
+            scale: 1,
+            highlights: FxHashMap::default(),
+        }
+    }
+
+    /// Draws each cell as a `scale` by `scale` square of pixels in images.
+    /// Terminal output is unaffected.
+    pub fn scale(mut self, scale: usize) -> Self {
+        self.scale = scale.max(1);
+        self
+    }
+
+    /// Draws the given cells in `colour`, regardless of their value. Later
+    /// highlights take precedence over earlier ones.
+    pub fn highlight(mut self, coords: impl IntoIterator<Item = Coordinate>, colour: Rgb) -> Self {
+        self.highlights
+            .extend(coords.into_iter().map(|c| (c, colour)));
+        self
+    }
+
+    /// The colour of the cell at `coord`, which must be in bounds.
+    fn colour_at(&self, coord: Coordinate) -> Rgb {
+        self.highlights
+            .get(&coord)
+            .copied()
+            .unwrap_or_else(|| (self.colour)(&self.grid[coord]))
+    }
+
+    /// The width and height of images, in pixels.
+    pub fn dimensions(&self) -> (usize, usize) {
+        (self.grid.m * self.scale, self.grid.n * self.scale)
+    }
+
+    /// The image as row-major RGB triples.
+    pub fn pixels(&self) -> Vec<u8> {
+        let (width, height) = self.dimensions();
+        let mut ret = Vec::with_capacity(width * height * 3);
+
+        for i in 0..self.grid.n {
+            let mut row = Vec::with_capacity(width * 3);
+            for j in 0..self.grid.m {
+                let Rgb(r, g, b) = self.colour_at((i, j).into());
+                for _ in 0..self.scale {
+                    row.extend_from_slice(&[r, g, b]);
+                }
+            }
+
+            for _ in 0..self.scale {
+                ret.extend_from_slice(&row);
+            }
+        }
+
+        ret
+    }
+
+    /// Writes the image as a binary (P6) PPM.
+    pub fn write_ppm(&self, mut out: impl Write) -> io::Result<()> {
+        let (width, height) = self.dimensions();
+        write!(out, "P6\n{} {}\n255\n", width, height)?;
+        out.write_all(&self.pixels())
+    }
+
+    /// Writes the image as a PNG.
+    #[cfg(feature = "png")]
+    pub fn write_png(&self, out: impl Write) -> io::Result<()> {
+        let (width, height) = self.dimensions();
+        let mut encoder = png::Encoder::new(out, width as u32, height as u32);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+
+        encoder
+            .write_header()
+            .and_then(|mut w| w.write_image_data(&self.pixels()))
+            .map_err(io::Error::other)
+    }
+
+    /// Writes the image to `path`, as a PNG or PPM depending on its
+    /// extension. PNG files are an error without the `png` feature.
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let path = path.as_ref();
+        let extension = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(|e| e.to_ascii_lowercase());
+
+        let write = match extension.as_deref() {
+            #[cfg(feature = "png")]
+            Some("png") => Self::write_png,
+            #[cfg(not(feature = "png"))]
+            Some("png") => {
+                return Err(io::Error::new(
+                    io::ErrorKind::Unsupported,
+                    "writing PNG images needs the png feature",
+                ))
+            }
+            Some("ppm") => Self::write_ppm,
+            _ => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("{} is not a .png or .ppm file", path.display()),
+                ))
+            }
+        };
+
+        let mut out = BufWriter::new(File::create(path)?);
+        write(self, &mut out)?;
+        out.flush()
+    }
+
+    /// Renders the grid with ANSI true-colour escape codes.
+    ///
+    /// Each character is a `▀` with the foreground set to one cell and the
+    /// background to the cell below it, so each line of output covers two
+    /// rows and the cells come out roughly square.
+    ///
+    /// ```
+    /// use aoc_common::{grid::Grid, render::{Renderer, Rgb}};
+    ///
+    /// let grid: Grid<u8> = vec![vec![0], vec![255]].into();
+    /// let ansi = Renderer::new(&grid, |v| Rgb(*v, *v, *v)).to_ansi();
+    ///
+    /// assert_eq!(ansi, "\x1b[38;2;0;0;0;48;2;255;255;255m▀\x1b[0m\n");
+    /// ```
+    pub fn to_ansi(&self) -> String {
+        let mut ret = String::new();
+
+        for i in (0..self.grid.n).step_by(2) {
+            for j in 0..self.grid.m {
+                let Rgb(r, g, b) = self.colour_at((i, j).into());
+                let _ = write!(ret, "\x1b[38;2;{};{};{}", r, g, b);
+
+                if i + 1 < self.grid.n {
+                    let Rgb(r, g, b) = self.colour_at((i + 1, j).into());
+                    let _ = write!(ret, ";48;2;{};{};{}m▀", r, g, b);
+                } else {
+                    ret.push_str("m▀");
+                }
+            }
+            ret.push_str("\x1b[0m\n");
+        }
+
+        ret
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn renderer(grid: &Grid<char>) -> Renderer<'_, char, impl Fn(&char) -> Rgb> {
+        Renderer::new(grid, |c| match c {
+            '#' => Rgb::WHITE,
+            _ => Rgb::BLACK,
+        })
+    }
+
+    #[test]
+    fn ppm_test() {
+        let grid: Grid<char> = "#.\n..".parse().unwrap();
+        let mut out = Vec::new();
+        renderer(&grid)
+            .highlight([Coordinate(1, 1)], Rgb(1, 2, 3))
+            .write_ppm(&mut out)
+            .unwrap();
+
+        let header = b"P6\n2 2\n255\n";
+        assert_eq!(&out[..header.len()], header);
+        assert_eq!(
+            &out[header.len()..],
+            &[255, 255, 255, 0, 0, 0, 0, 0, 0, 1, 2, 3]
+        );
+    }
+
+    #[test]
+    fn scale_test() {
+        let grid: Grid<char> = "#.".parse().unwrap();
+        let r = renderer(&grid).scale(2);
+
+        assert_eq!(r.dimensions(), (4, 2));
+        let row = [255, 255, 255, 255, 255, 255, 0, 0, 0, 0, 0, 0];
+        assert_eq!(r.pixels(), [row, row].concat());
+    }
+
+    #[test]
+    #[cfg(feature = "png")]
+    fn png_test() {
+        let grid: Grid<char> = "#.#\n.#.".parse().unwrap();
+        let mut out = Vec::new();
+        renderer(&grid).write_png(&mut out).unwrap();
+
+        let decoder = png::Decoder::new(out.as_slice());
+        let mut reader = decoder.read_info().unwrap();
+        let mut buf = vec![0; reader.output_buffer_size()];
+        let info = reader.next_frame(&mut buf).unwrap();
+
+        assert_eq!((info.width, info.height), (3, 2));
+        assert_eq!(&buf[..info.buffer_size()], renderer(&grid).pixels());
+    }
+
+    #[test]
+    fn ansi_odd_rows_test() {
+        let grid: Grid<char> = "#\n.\n#".parse().unwrap();
+        let ansi = renderer(&grid).to_ansi();
+
+        assert_eq!(ansi.lines().count(), 2);
+        assert!(ansi.ends_with("\x1b[38;2;255;255;255m▀\x1b[0m\n"));
+    }
+}
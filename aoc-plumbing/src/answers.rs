@@ -0,0 +1,232 @@
+use std::{collections::BTreeMap, fmt::Display, path::Path, str::FromStr};
+
+use serde::{Deserialize, Deserializer, Serialize};
+
+use crate::{Problem, Solution};
+
+#[derive(Debug, thiserror::Error)]
+pub enum AnswersError {
+    #[error("could not read answers file: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("could not parse answers file: {0}")]
+    Parse(#[from] toml::de::Error),
+
+    #[error("invalid day key: {0:?}")]
+    InvalidDay(String),
+}
+
+/// The expected answers for a single day. Either part may be omitted, in which
+/// case that part will be reported as missing rather than failing.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+pub struct ExpectedAnswer {
+    #[serde(default, deserialize_with = "deserialize_answer")]
+    pub part_one: Option<String>,
+    #[serde(default, deserialize_with = "deserialize_answer")]
+    pub part_two: Option<String>,
+}
+
+/// A set of known answers keyed by day, typically loaded from the
+/// workspace-level `answers.toml`.
+///
+/// Answers may be written as either integers or strings, and are compared
+/// against the `Display` output of a solution.
+///
+/// # Usage
+///
+/// ```
+/// use aoc_plumbing::{answers::{Answers, PartCheck}, Solution};
+///
+/// let answers: Answers = r#"
+/// [1]
+/// part_one = 11
+/// part_two = "31"
+///
+/// [17]
+/// part_one = "4,6,3,5,6,3,5,2,1,0"
+/// "#.parse().unwrap();
+///
+/// let [one, two] = answers.check(1, &Solution::new(11, 30));
+/// assert_eq!(one, PartCheck::Pass);
+/// assert_eq!(
+///     two,
+///     PartCheck::Fail { expected: "31".into(), actual: "30".into() }
+/// );
+///
+/// let [_, two] = answers.check(17, &Solution::new("4,6,3,5,6,3,5,2,1,0", 0));
+/// assert_eq!(two, PartCheck::Missing);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Answers {
+    days: BTreeMap<usize, ExpectedAnswer>,
+}
+
+impl FromStr for Answers {
+    type Err = AnswersError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let raw: BTreeMap<String, ExpectedAnswer> = toml::from_str(s)?;
+        let days = raw
+            .into_iter()
+            .map(|(k, v)| {
+                k.trim_start_matches("day-")
+                    .parse::<usize>()
+                    .map(|day| (day, v))
+                    .map_err(|_| AnswersError::InvalidDay(k))
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self { days })
+    }
+}
+
+impl Answers {
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, AnswersError> {
+        std::fs::read_to_string(path)?.parse()
+    }
+
+    pub fn get(&self, day: usize) -> Option<&ExpectedAnswer> {
+        self.days.get(&day)
+    }
+
+    /// Compares both parts of the given solution against the expected
+    /// answers for `day`.
+    pub fn check<T, G>(&self, day: usize, solution: &Solution<T, G>) -> [PartCheck; 2]
+    where
+        T: Display + Serialize + PartialEq,
+        G: Display + Serialize + PartialEq,
+    {
+        let expected = self.get(day);
+        [
+            PartCheck::new(
+                expected.and_then(|e| e.part_one.as_deref()),
+                &solution.part_one,
+            ),
+            PartCheck::new(
+                expected.and_then(|e| e.part_two.as_deref()),
+                &solution.part_two,
+            ),
+        ]
+    }
+}
+
+/// The result of checking a single part against its expected answer.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum PartCheck {
+    Pass,
+    Fail { expected: String, actual: String },
+    Missing,
+}
+
+impl PartCheck {
+    pub fn new(expected: Option<&str>, actual: &impl Display) -> Self {
+        match expected {
+            None => Self::Missing,
+            Some(expected) => {
+                let actual = actual.to_string();
+                if expected == actual {
+                    Self::Pass
+                } else {
+                    Self::Fail {
+                        expected: expected.to_string(),
+                        actual,
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn is_failure(&self) -> bool {
+        matches!(self, Self::Fail { .. })
+    }
+}
+
+impl Display for PartCheck {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Pass => write!(f, "pass"),
+            Self::Missing => write!(f, "missing"),
+            Self::Fail { expected, actual } => {
+                write!(
+                    f,
+                    "FAIL\n    - expected: {}\n    + actual:   {}",
+                    expected, actual
+                )
+            }
+        }
+    }
+}
+
+/// Solves `T` against the given input and panics with a diff if either part
+/// disagrees with the stored answers. Parts without a stored answer are
+/// skipped, but a day with no stored answers at all is treated as an error.
+///
+/// This is normally invoked through [`answers_test!`](crate::answers_test).
+pub fn assert_answers<T>(answers: impl AsRef<Path>, input: impl AsRef<Path>)
+where
+    T: Problem,
+    <T as Problem>::ProblemError: std::fmt::Debug,
+{
+    let answers = Answers::load(answers).expect("Unable to load answers");
+    assert!(
+        answers.get(T::DAY).is_some(),
+        "no stored answers for day {}",
+        T::DAY
+    );
+
+    let input = std::fs::read_to_string(input).expect("Unable to load input");
+    let solution = T::solve(&input).unwrap();
+
+    let failures = answers
+        .check(T::DAY, &solution)
+        .into_iter()
+        .enumerate()
+        .filter(|(_, c)| c.is_failure())
+        .map(|(i, c)| format!("part {}: {}", i + 1, c))
+        .collect::<Vec<_>>();
+
+    assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+}
+
+/// Generates an ignored `stored_answers` test that checks the given problem's
+/// `input.txt` against the workspace-level `answers.toml`.
+///
+/// ```text
+/// #[cfg(test)]
+/// mod tests {
+///     use super::*;
+///
+///     aoc_plumbing::answers_test!(HistorianHysteria);
+/// }
+/// ```
+#[macro_export]
+macro_rules! answers_test {
+    ($problem:ty) => {
+        #[test]
+        #[ignore]
+        fn stored_answers() {
+            $crate::answers::assert_answers::<$problem>(
+                concat!(env!("CARGO_MANIFEST_DIR"), "/../answers.toml"),
+                concat!(env!("CARGO_MANIFEST_DIR"), "/input.txt"),
+            );
+        }
+    };
+}
+
+fn deserialize_answer<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Raw {
+        Int(i64),
+        Str(String),
+    }
+
+    Ok(Some(match Raw::deserialize(deserializer)? {
+        Raw::Int(v) => v.to_string(),
+        Raw::Str(v) => v,
+    }))
+}
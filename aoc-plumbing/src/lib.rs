@@ -1,3 +1,13 @@
+pub mod answers;
+pub mod parse;
 pub mod problem;
+pub mod registry;
+pub mod timing;
+pub mod workspace;
 
-pub use problem::{Problem, Solution};
+pub use parse::{ParseError, ParseErrorContext};
+pub use problem::{Part, PartSolution, Problem, Solution};
+pub use timing::Timing;
+
+#[doc(hidden)]
+pub use inventory;
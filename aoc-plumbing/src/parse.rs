@@ -0,0 +1,215 @@
+use std::{convert::Infallible, fmt::Display};
+
+/// An error encountered while parsing a problem's input, carrying the location
+/// of the offending text so that it can be reported back to the user.
+///
+/// Lines and columns are 1-based, with columns counted in chars. The byte
+/// `offset` and `len` of the snippet are kept as well, so that an error raised
+/// while parsing a fragment of the input can be relocated relative to the full
+/// input with [`ParseError::within`].
+///
+/// # Usage
+///
+/// ```
+/// use aoc_plumbing::ParseError;
+///
+/// let input = "1|2\n3|x\n";
+/// let line = input.lines().nth(1).unwrap();
+/// let err = ParseError::at(input, &line[2..], "invalid page number");
+///
+/// assert_eq!((err.line, err.column), (2, 3));
+/// assert_eq!(err.snippet, "x");
+/// assert_eq!(
+///     err.to_string(),
+///     "invalid page number at line 2, column 3: \"x\""
+/// );
+/// assert_eq!(
+///     err.render(input),
+///     "error: invalid page number\n --> line 2, column 3\n  |\n2 | 3|x\n  |   ^"
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("{message} at line {line}, column {column}: {snippet:?}")]
+pub struct ParseError {
+    pub message: String,
+    pub line: usize,
+    pub column: usize,
+    pub snippet: String,
+    pub offset: usize,
+    pub len: usize,
+}
+
+impl ParseError {
+    /// Creates an error for the `len` bytes at byte `offset` in `input`.
+    ///
+    /// The offset and length are clamped to the input and adjusted to the
+    /// nearest char boundaries, so this will not panic on bad positions.
+    pub fn at_offset(input: &str, offset: usize, len: usize, message: impl Into<String>) -> Self {
+        let mut offset = offset.min(input.len());
+        while !input.is_char_boundary(offset) {
+            offset -= 1;
+        }
+
+        let mut end = offset.saturating_add(len).min(input.len());
+        while !input.is_char_boundary(end) {
+            end += 1;
+        }
+
+        let line_start = input[..offset].rfind('\n').map(|i| i + 1).unwrap_or(0);
+        let line = input[..offset].matches('\n').count() + 1;
+        let column = input[line_start..offset].chars().count() + 1;
+        let snippet = input[offset..end].to_string();
+
+        Self {
+            message: message.into(),
+            line,
+            column,
+            snippet,
+            offset,
+            len: end - offset,
+        }
+    }
+
+    /// Creates an error for `fragment`, which should be a subslice of `input`
+    /// (as produced by `lines`, `split`, and friends). If it is not, the first
+    /// occurrence of `fragment` in `input` is used instead, falling back to the
+    /// end of the input.
+    pub fn at(input: &str, fragment: &str, message: impl Into<String>) -> Self {
+        let offset = subslice_offset(input, fragment)
+            .or_else(|| input.find(fragment))
+            .unwrap_or(input.len());
+
+        Self::at_offset(input, offset, fragment.len(), message)
+    }
+
+    /// Creates an error located at the very end of `input`, for when more input
+    /// was expected.
+    pub fn at_end(input: &str, message: impl Into<String>) -> Self {
+        Self::at_offset(input, input.len(), 0, message)
+    }
+
+    /// Creates an error covering the line of `input` at `index`, counting from
+    /// 0, for errors that only know which row of a grid they are in. Falls
+    /// back to the end of the input if there are not that many lines.
+    ///
+    /// ```
+    /// use aoc_plumbing::ParseError;
+    ///
+    /// let err = ParseError::at_line("#.#\n#.\n###", 1, "ragged row");
+    ///
+    /// assert_eq!((err.line, err.column), (2, 1));
+    /// assert_eq!(err.snippet, "#.");
+    /// ```
+    pub fn at_line(input: &str, index: usize, message: impl Into<String>) -> Self {
+        match input.lines().nth(index) {
+            Some(line) => Self::at(input, line, message),
+            None => Self::at_end(input, message),
+        }
+    }
+
+    /// Relocates an error that was raised while parsing `fragment` so that it
+    /// is relative to `input`, of which `fragment` is a subslice.
+    ///
+    /// ```
+    /// use aoc_plumbing::ParseError;
+    ///
+    /// let input = "1 2\n3 x\n";
+    /// let line = input.lines().nth(1).unwrap();
+    /// let err = ParseError::at(line, &line[2..], "invalid level").within(input, line);
+    ///
+    /// assert_eq!((err.line, err.column), (2, 3));
+    /// ```
+    pub fn within(self, input: &str, fragment: &str) -> Self {
+        let base = subslice_offset(input, fragment)
+            .or_else(|| input.find(fragment))
+            .unwrap_or(0);
+
+        Self::at_offset(input, base + self.offset, self.len, self.message)
+    }
+
+    /// Renders the line of `input` containing this error, with the offending
+    /// snippet underlined.
+    pub fn render(&self, input: &str) -> String {
+        let line = input.lines().nth(self.line - 1).unwrap_or_default();
+        let number = self.line.to_string();
+        let pad = " ".repeat(number.len());
+
+        // only underline the part of the snippet that is on the error's line
+        let width = self
+            .snippet
+            .lines()
+            .next()
+            .map(|s| s.chars().count())
+            .unwrap_or(0)
+            .max(1);
+
+        format!(
+            "error: {}\n{}--> line {}, column {}\n{} |\n{} | {}\n{} | {}{}",
+            self.message,
+            pad,
+            self.line,
+            self.column,
+            pad,
+            number,
+            line,
+            pad,
+            " ".repeat(self.column - 1),
+            "^".repeat(width),
+        )
+    }
+}
+
+impl From<Infallible> for ParseError {
+    fn from(value: Infallible) -> Self {
+        match value {}
+    }
+}
+
+/// Convenience methods for converting failed results and missing options into
+/// located [`ParseError`]s.
+///
+/// ```
+/// use aoc_plumbing::{ParseError, ParseErrorContext};
+///
+/// let input = "12 abc";
+/// let word = input.split(' ').nth(1).unwrap();
+/// let err = word.parse::<u32>().parse_err_at(input, word, "invalid number").unwrap_err();
+///
+/// assert_eq!(err.column, 4);
+/// assert_eq!(err.message, "invalid number: invalid digit found in string");
+///
+/// let end = &input[input.len()..];
+/// let err = input.split(' ').nth(2).parse_err_at(input, end, "missing value").unwrap_err();
+/// assert_eq!(err, ParseError::at_end(input, "missing value"));
+/// ```
+pub trait ParseErrorContext<T> {
+    /// Converts a failure into a [`ParseError`] located at `fragment`, which
+    /// should be a subslice of `input`.
+    fn parse_err_at(self, input: &str, fragment: &str, message: &str) -> Result<T, ParseError>;
+}
+
+impl<T, E> ParseErrorContext<T> for Result<T, E>
+where
+    E: Display,
+{
+    fn parse_err_at(self, input: &str, fragment: &str, message: &str) -> Result<T, ParseError> {
+        self.map_err(|e| ParseError::at(input, fragment, format!("{}: {}", message, e)))
+    }
+}
+
+impl<T> ParseErrorContext<T> for Option<T> {
+    fn parse_err_at(self, input: &str, fragment: &str, message: &str) -> Result<T, ParseError> {
+        self.ok_or_else(|| ParseError::at(input, fragment, message))
+    }
+}
+
+fn subslice_offset(input: &str, fragment: &str) -> Option<usize> {
+    let start = input.as_ptr() as usize;
+    let frag = fragment.as_ptr() as usize;
+
+    if frag >= start && frag + fragment.len() <= start + input.len() {
+        Some(frag - start)
+    } else {
+        None
+    }
+}
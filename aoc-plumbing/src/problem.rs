@@ -1,6 +1,8 @@
-use std::{fmt::Display, str::FromStr};
+use std::{fmt::Display, str::FromStr, time::Instant};
 
-use serde::Serialize;
+use serde::{ser::SerializeMap, Serialize, Serializer};
+
+use crate::Timing;
 
 /// This struct enables printing a given solution in either plaintext or JSON,
 /// depending on the presence of the `AOC_OUTPUT_JSON` ENV var. Its main purpose
@@ -31,6 +33,8 @@ where
 {
     pub part_one: T,
     pub part_two: G,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timing: Option<Timing>,
 }
 
 /// The default implementation of `Solution` is as follows:
@@ -53,7 +57,38 @@ where
     G: Display + Serialize + PartialEq,
 {
     pub fn new(part_one: T, part_two: G) -> Self {
-        Self { part_one, part_two }
+        Self {
+            part_one,
+            part_two,
+            timing: None,
+        }
+    }
+
+    /// Attaches the given timings to this solution, which will then be
+    /// included in both the plaintext and JSON output.
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use aoc_plumbing::{Solution, Timing};
+    /// let t = Timing::new(
+    ///     Duration::from_micros(1),
+    ///     Duration::from_micros(2),
+    ///     Duration::from_micros(3),
+    /// );
+    /// let s = Solution::new(1, 2).with_timing(t);
+    ///
+    /// assert_eq!(
+    ///     s.to_string(),
+    ///     "part 1: 1\npart 2: 2\nparse: 1µs\npart 1 time: 2µs\npart 2 time: 3µs\ntotal time: 6µs"
+    /// );
+    /// assert_eq!(
+    ///     serde_json::to_string(&s).unwrap(),
+    ///     "{\"part_one\":1,\"part_two\":2,\"timing\":{\"parse_ns\":1000,\"part_one_ns\":2000,\"part_two_ns\":3000,\"total_ns\":6000}}"
+    /// );
+    /// ```
+    pub fn with_timing(mut self, timing: Timing) -> Self {
+        self.timing = Some(timing);
+        self
     }
 }
 
@@ -63,7 +98,13 @@ where
     G: Display + Serialize + PartialEq,
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "part 1: {}\npart 2: {}", self.part_one, self.part_two)
+        write!(f, "part 1: {}\npart 2: {}", self.part_one, self.part_two)?;
+
+        if let Some(timing) = self.timing {
+            write!(f, "\n{}", timing)?;
+        }
+
+        Ok(())
     }
 }
 
@@ -77,6 +118,131 @@ where
     }
 }
 
+/// One of the two parts of a problem.
+///
+/// ```
+/// use aoc_plumbing::Part;
+///
+/// assert_eq!("1".parse::<Part>().unwrap(), Part::One);
+/// assert_eq!("two".parse::<Part>().unwrap(), Part::Two);
+/// assert!("3".parse::<Part>().is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub fn number(&self) -> usize {
+        match self {
+            Self::One => 1,
+            Self::Two => 2,
+        }
+    }
+
+    /// The key used for this part in serialized solutions.
+    pub fn key(&self) -> &'static str {
+        match self {
+            Self::One => "part_one",
+            Self::Two => "part_two",
+        }
+    }
+}
+
+impl FromStr for Part {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "1" | "one" => Ok(Self::One),
+            "2" | "two" => Ok(Self::Two),
+            _ => Err(format!("invalid part {:?}, expected 1 or 2", s)),
+        }
+    }
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.number())
+    }
+}
+
+/// A solution for only one part of a problem. This mirrors `Solution`, but
+/// with only the key for the solved part.
+///
+/// # Usage
+///
+/// ```
+/// use aoc_plumbing::{Part, PartSolution};
+/// let s = PartSolution::new(Part::Two, 12345);
+///
+/// assert_eq!(s.to_string(), "part 2: 12345");
+/// assert_eq!(
+///     serde_json::to_string(&s).unwrap(),
+///     "{\"part_two\":12345}".to_string()
+/// );
+/// ```
+#[derive(Debug, PartialEq)]
+pub struct PartSolution<T>
+where
+    T: Display + Serialize + PartialEq,
+{
+    pub part: Part,
+    pub answer: T,
+    pub timing: Option<Timing>,
+}
+
+impl<T> PartSolution<T>
+where
+    T: Display + Serialize + PartialEq,
+{
+    pub fn new(part: Part, answer: T) -> Self {
+        Self {
+            part,
+            answer,
+            timing: None,
+        }
+    }
+
+    pub fn with_timing(mut self, timing: Timing) -> Self {
+        self.timing = Some(timing);
+        self
+    }
+}
+
+impl<T> Display for PartSolution<T>
+where
+    T: Display + Serialize + PartialEq,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "part {}: {}", self.part, self.answer)?;
+
+        if let Some(timing) = self.timing {
+            write!(f, "\n{}", timing)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<T> Serialize for PartSolution<T>
+where
+    T: Display + Serialize + PartialEq,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut m = serializer.serialize_map(None)?;
+        m.serialize_entry(self.part.key(), &self.answer)?;
+        if let Some(ref timing) = self.timing {
+            m.serialize_entry("timing", timing)?;
+        }
+        m.end()
+    }
+}
+
 pub trait Problem: FromStr {
     const DAY: usize;
     const TITLE: &'static str;
@@ -98,6 +264,68 @@ pub trait Problem: FromStr {
         Ok(Solution::new(inst.part_one()?, inst.part_two()?))
     }
 
+    /// Like `solve`, but measures the time taken by `instance`, `part_one`,
+    /// and `part_two` separately and attaches them to the solution.
+    fn solve_timed(raw_input: &str) -> Result<Solution<Self::P1, Self::P2>, Self::ProblemError> {
+        let start = Instant::now();
+        let mut inst = Self::instance(raw_input)?;
+        let parse = start.elapsed();
+
+        let start = Instant::now();
+        let part_one = inst.part_one()?;
+        let part_one_time = start.elapsed();
+
+        let start = Instant::now();
+        let part_two = inst.part_two()?;
+        let part_two_time = start.elapsed();
+
+        Ok(Solution::new(part_one, part_two).with_timing(Timing::new(
+            parse,
+            part_one_time,
+            part_two_time,
+        )))
+    }
+
+    /// Parses the input and solves only part one. The time taken for each
+    /// stage is always recorded on the returned solution.
+    fn solve_part_one(raw_input: &str) -> Result<PartSolution<Self::P1>, Self::ProblemError> {
+        let start = Instant::now();
+        let mut inst = Self::instance(raw_input)?;
+        let parse = start.elapsed();
+
+        let start = Instant::now();
+        let answer = inst.part_one()?;
+        let elapsed = start.elapsed();
+
+        Ok(
+            PartSolution::new(Part::One, answer).with_timing(Timing::single(
+                Part::One,
+                parse,
+                elapsed,
+            )),
+        )
+    }
+
+    /// Parses the input and solves only part two. The time taken for each
+    /// stage is always recorded on the returned solution.
+    fn solve_part_two(raw_input: &str) -> Result<PartSolution<Self::P2>, Self::ProblemError> {
+        let start = Instant::now();
+        let mut inst = Self::instance(raw_input)?;
+        let parse = start.elapsed();
+
+        let start = Instant::now();
+        let answer = inst.part_two()?;
+        let elapsed = start.elapsed();
+
+        Ok(
+            PartSolution::new(Part::Two, answer).with_timing(Timing::single(
+                Part::Two,
+                parse,
+                elapsed,
+            )),
+        )
+    }
+
     fn problem_label() -> String {
         format!(
             "{:03} {}",
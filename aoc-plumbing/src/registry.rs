@@ -0,0 +1,236 @@
+use std::{error::Error, marker::PhantomData, str::FromStr};
+
+use crate::{Part, PartSolution, Problem, Solution};
+
+/// The error type returned by a type-erased problem.
+pub type DynError = Box<dyn Error + Send + Sync + 'static>;
+
+/// An object-safe view of a [`Problem`], whose answers have been converted to
+/// strings so that solutions for different days can be handled uniformly.
+///
+/// Implementations are not normally written by hand; any `Problem` can be
+/// wrapped with [`Erased`], and registered with
+/// [`register_problem!`](crate::register_problem).
+///
+/// # Usage
+///
+/// ```
+/// use std::str::FromStr;
+/// use aoc_plumbing::{registry::{DynProblem, Erased}, Part, Problem, Solution};
+///
+/// struct Example(i64);
+///
+/// impl FromStr for Example {
+///     type Err = std::num::ParseIntError;
+///
+///     fn from_str(s: &str) -> Result<Self, Self::Err> {
+///         Ok(Self(s.trim().parse()?))
+///     }
+/// }
+///
+/// impl Problem for Example {
+///     const DAY: usize = 99;
+///     const TITLE: &'static str = "an example";
+///     const README: &'static str = "";
+///
+///     type ProblemError = std::num::ParseIntError;
+///     type P1 = i64;
+///     type P2 = String;
+///
+///     fn part_one(&mut self) -> Result<Self::P1, Self::ProblemError> {
+///         Ok(self.0 * 2)
+///     }
+///
+///     fn part_two(&mut self) -> Result<Self::P2, Self::ProblemError> {
+///         Ok(format!("{}!", self.0))
+///     }
+/// }
+///
+/// let problem: &dyn DynProblem = &Erased::<Example>::new();
+///
+/// assert_eq!(problem.name(), "an-example");
+/// assert_eq!(problem.problem_label(), "099 an example");
+/// assert_eq!(
+///     problem.solve("21").unwrap(),
+///     Solution::new("42".to_string(), "21!".to_string())
+/// );
+/// assert_eq!(problem.solve_part("21", Part::Two).unwrap().answer, "21!");
+/// assert!(problem.solve("foo").is_err());
+/// ```
+pub trait DynProblem: Send + Sync {
+    fn day(&self) -> usize;
+    fn title(&self) -> &'static str;
+    fn readme(&self) -> &'static str;
+
+    fn solve(&self, raw_input: &str) -> Result<Solution<String, String>, DynError>;
+    fn solve_timed(&self, raw_input: &str) -> Result<Solution<String, String>, DynError>;
+
+    /// Solves only the given part. As with [`Problem::solve_part_one`], the
+    /// timings are always recorded.
+    fn solve_part(&self, raw_input: &str, part: Part) -> Result<PartSolution<String>, DynError>;
+
+    /// The hyphenated title, as used for the day's cli subcommand.
+    fn name(&self) -> String {
+        self.title().replace(' ', "-")
+    }
+
+    fn problem_label(&self) -> String {
+        format!("{} {}", self.padded_day(), self.title())
+    }
+
+    fn padded_day(&self) -> String {
+        format!("{:03}", self.day())
+    }
+
+    fn long_description(&self) -> String {
+        format!("{} {}", self.padded_day(), self.readme())
+    }
+}
+
+/// Wraps a [`Problem`] so that it can be used as a [`DynProblem`].
+///
+/// If solving fails because the input could not be parsed, the error returned
+/// is the problem's own parse error rather than its `ProblemError`, so that
+/// consumers can downcast it to (for instance) a [`ParseError`](crate::ParseError)
+/// to report where in the input things went wrong.
+pub struct Erased<T>(PhantomData<fn() -> T>);
+
+impl<T> Erased<T> {
+    pub const fn new() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<T> Default for Erased<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> DynProblem for Erased<T>
+where
+    T: Problem,
+    <T as Problem>::ProblemError: Into<DynError>,
+    <T as FromStr>::Err: Into<DynError>,
+{
+    fn day(&self) -> usize {
+        T::DAY
+    }
+
+    fn title(&self) -> &'static str {
+        T::TITLE
+    }
+
+    fn readme(&self) -> &'static str {
+        T::README
+    }
+
+    fn solve(&self, raw_input: &str) -> Result<Solution<String, String>, DynError> {
+        T::solve(raw_input)
+            .map(stringify)
+            .map_err(|e| erase_error::<T>(raw_input, e))
+    }
+
+    fn solve_timed(&self, raw_input: &str) -> Result<Solution<String, String>, DynError> {
+        T::solve_timed(raw_input)
+            .map(stringify)
+            .map_err(|e| erase_error::<T>(raw_input, e))
+    }
+
+    fn solve_part(&self, raw_input: &str, part: Part) -> Result<PartSolution<String>, DynError> {
+        match part {
+            Part::One => T::solve_part_one(raw_input).map(stringify_part),
+            Part::Two => T::solve_part_two(raw_input).map(stringify_part),
+        }
+        .map_err(|e| erase_error::<T>(raw_input, e))
+    }
+}
+
+/// Converts a failure from solving `T`, preferring the parse error if the
+/// failure was caused by the input being invalid. As the `ProblemError` has
+/// already swallowed any parse error by this point, the input is parsed again
+/// to recover it; this only happens on the failure path.
+fn erase_error<T>(raw_input: &str, e: <T as Problem>::ProblemError) -> DynError
+where
+    T: Problem,
+    <T as Problem>::ProblemError: Into<DynError>,
+    <T as FromStr>::Err: Into<DynError>,
+{
+    match T::instance(raw_input) {
+        Err(parse_error) => parse_error.into(),
+        Ok(_) => e.into(),
+    }
+}
+
+fn stringify<T, G>(solution: Solution<T, G>) -> Solution<String, String>
+where
+    T: std::fmt::Display + serde::Serialize + PartialEq,
+    G: std::fmt::Display + serde::Serialize + PartialEq,
+{
+    Solution {
+        part_one: solution.part_one.to_string(),
+        part_two: solution.part_two.to_string(),
+        timing: solution.timing,
+    }
+}
+
+fn stringify_part<T>(solution: PartSolution<T>) -> PartSolution<String>
+where
+    T: std::fmt::Display + serde::Serialize + PartialEq,
+{
+    PartSolution {
+        part: solution.part,
+        answer: solution.answer.to_string(),
+        timing: solution.timing,
+    }
+}
+
+/// An entry in the global problem registry. These are created by
+/// [`register_problem!`](crate::register_problem).
+pub struct Registration {
+    problem: &'static dyn DynProblem,
+}
+
+impl Registration {
+    pub const fn new(problem: &'static dyn DynProblem) -> Self {
+        Self { problem }
+    }
+}
+
+inventory::collect!(Registration);
+
+/// Returns every registered problem, ordered by day.
+///
+/// Only problems from crates that are linked into the final binary will be
+/// present, so consumers must depend on (and reference) each day's crate.
+pub fn problems() -> Vec<&'static dyn DynProblem> {
+    let mut problems = inventory::iter::<Registration>
+        .into_iter()
+        .map(|r| r.problem)
+        .collect::<Vec<_>>();
+    problems.sort_by_key(|p| p.day());
+    problems
+}
+
+/// Returns the registered problem for the given day, if there is one.
+pub fn find(day: usize) -> Option<&'static dyn DynProblem> {
+    inventory::iter::<Registration>
+        .into_iter()
+        .map(|r| r.problem)
+        .find(|p| p.day() == day)
+}
+
+/// Adds the given [`Problem`] to the global registry so that it can be found
+/// at runtime via [`problems`] and [`find`].
+///
+/// ```text
+/// aoc_plumbing::register_problem!(HistorianHysteria);
+/// ```
+#[macro_export]
+macro_rules! register_problem {
+    ($problem:ty) => {
+        $crate::inventory::submit! {
+            $crate::registry::Registration::new(&$crate::registry::Erased::<$problem>::new())
+        }
+    };
+}
@@ -0,0 +1,118 @@
+use std::{fmt::Display, time::Duration};
+
+use serde::{Serialize, Serializer};
+
+use crate::Part;
+
+/// Wall-clock timings for the individual stages of solving a problem.
+///
+/// The time for a part is absent if that part was not solved. Durations are
+/// serialized as whole nanoseconds so they can be consumed without having to
+/// understand `Duration`'s serde representation.
+///
+/// # Usage
+///
+/// ```
+/// use std::time::Duration;
+/// use aoc_plumbing::{Part, Timing};
+/// let t = Timing::new(
+///     Duration::from_micros(5),
+///     Duration::from_micros(10),
+///     Duration::from_micros(20),
+/// );
+///
+/// assert_eq!(t.total(), Duration::from_micros(35));
+/// assert_eq!(
+///     serde_json::to_string(&t).unwrap(),
+///     "{\"parse_ns\":5000,\"part_one_ns\":10000,\"part_two_ns\":20000,\"total_ns\":35000}"
+/// );
+///
+/// let t = Timing::single(Part::Two, Duration::from_micros(5), Duration::from_micros(20));
+///
+/// assert_eq!(t.total(), Duration::from_micros(25));
+/// assert_eq!(
+///     serde_json::to_string(&t).unwrap(),
+///     "{\"parse_ns\":5000,\"part_two_ns\":20000,\"total_ns\":25000}"
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Timing {
+    pub parse: Duration,
+    pub part_one: Option<Duration>,
+    pub part_two: Option<Duration>,
+}
+
+impl Timing {
+    pub fn new(parse: Duration, part_one: Duration, part_two: Duration) -> Self {
+        Self {
+            parse,
+            part_one: Some(part_one),
+            part_two: Some(part_two),
+        }
+    }
+
+    /// Timings for when only the given part was solved.
+    pub fn single(part: Part, parse: Duration, duration: Duration) -> Self {
+        match part {
+            Part::One => Self {
+                parse,
+                part_one: Some(duration),
+                part_two: None,
+            },
+            Part::Two => Self {
+                parse,
+                part_one: None,
+                part_two: Some(duration),
+            },
+        }
+    }
+
+    pub fn total(&self) -> Duration {
+        self.parse + self.part_one.unwrap_or_default() + self.part_two.unwrap_or_default()
+    }
+}
+
+impl Display for Timing {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "parse: {:?}", self.parse)?;
+
+        if let Some(d) = self.part_one {
+            writeln!(f, "part 1 time: {:?}", d)?;
+        }
+
+        if let Some(d) = self.part_two {
+            writeln!(f, "part 2 time: {:?}", d)?;
+        }
+
+        write!(f, "total time: {:?}", self.total())
+    }
+}
+
+impl Serialize for Timing {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        use serde::ser::SerializeStruct;
+
+        let mut s = serializer.serialize_struct("Timing", 4)?;
+        s.serialize_field("parse_ns", &as_nanos(self.parse))?;
+
+        match self.part_one {
+            Some(d) => s.serialize_field("part_one_ns", &as_nanos(d))?,
+            None => s.skip_field("part_one_ns")?,
+        }
+
+        match self.part_two {
+            Some(d) => s.serialize_field("part_two_ns", &as_nanos(d))?,
+            None => s.skip_field("part_two_ns")?,
+        }
+
+        s.serialize_field("total_ns", &as_nanos(self.total()))?;
+        s.end()
+    }
+}
+
+fn as_nanos(d: Duration) -> u64 {
+    d.as_nanos().try_into().unwrap_or(u64::MAX)
+}
@@ -0,0 +1,77 @@
+//! Helpers for the tools that work with every day in the workspace, so that
+//! the cli and the benchmark runners find and link days the same way.
+use std::{
+    env,
+    fmt::Write,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+/// Finds `<root>/day-NNN-*/input.txt` for the given day, or `None` if there
+/// is no such file.
+pub fn find_input(root: &Path, day: usize) -> io::Result<Option<PathBuf>> {
+    let prefix = format!("day-{:03}-", day);
+
+    for entry in fs::read_dir(root)? {
+        let entry = entry?;
+        if entry.file_type()?.is_dir() && entry.file_name().to_string_lossy().starts_with(&prefix) {
+            let input = entry.path().join("input.txt");
+            if input.is_file() {
+                return Ok(Some(input));
+            }
+        }
+    }
+
+    Ok(None)
+}
+
+/// Writes `days.rs` to `OUT_DIR` for a build script, with an
+/// `extern crate ... as _;` for each `day-*` path dependency in the manifest
+/// of the crate being built.
+///
+/// Every day registers itself with the problem registry, but a crate is only
+/// linked if something references it. Including the generated file from the
+/// crate root means adding a day to `Cargo.toml` is all that's needed to make
+/// it available.
+///
+/// ```text
+/// // build.rs
+/// fn main() {
+///     aoc_plumbing::workspace::link_days();
+/// }
+///
+/// // main.rs
+/// include!(concat!(env!("OUT_DIR"), "/days.rs"));
+/// ```
+///
+/// # Panics
+///
+/// If it is not run from a build script, or the manifest cannot be read.
+pub fn link_days() {
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").expect("CARGO_MANIFEST_DIR not set");
+    let manifest_path = Path::new(&manifest_dir).join("Cargo.toml");
+    println!("cargo:rerun-if-changed={}", manifest_path.display());
+
+    let manifest: toml::Table = fs::read_to_string(&manifest_path)
+        .expect("Could not read Cargo.toml")
+        .parse()
+        .expect("Could not parse Cargo.toml");
+
+    let mut out = String::new();
+    if let Some(deps) = manifest.get("dependencies").and_then(|d| d.as_table()) {
+        for (name, dep) in deps.iter() {
+            let is_day = dep
+                .get("path")
+                .and_then(|p| p.as_str())
+                .map(|p| p.starts_with("../day-"))
+                .unwrap_or(false);
+
+            if is_day {
+                writeln!(out, "extern crate {} as _;", name.replace('-', "_")).unwrap();
+            }
+        }
+    }
+
+    let out_dir = env::var("OUT_DIR").expect("OUT_DIR not set");
+    fs::write(Path::new(&out_dir).join("days.rs"), out).expect("Could not write days.rs");
+}
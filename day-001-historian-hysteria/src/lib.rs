@@ -1,7 +1,6 @@
 use std::{collections::BinaryHeap, str::FromStr};
 
-use anyhow::bail;
-use aoc_plumbing::Problem;
+use aoc_plumbing::{ParseError, ParseErrorContext, Problem};
 use rustc_hash::FxHashMap;
 
 #[derive(Debug, Clone)]
@@ -11,7 +10,7 @@ pub struct HistorianHysteria {
 }
 
 impl FromStr for HistorianHysteria {
-    type Err = anyhow::Error;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let mut left = BinaryHeap::default();
@@ -20,12 +19,12 @@ impl FromStr for HistorianHysteria {
 
         for line in s.lines() {
             if let Some((a, b)) = line.split_once("   ") {
-                left.push(a.parse::<u32>()?);
-                let parsed_b = b.parse::<u32>()?;
+                left.push(a.parse::<u32>().parse_err_at(s, a, "invalid location id")?);
+                let parsed_b = b.parse::<u32>().parse_err_at(s, b, "invalid location id")?;
                 right.push(parsed_b);
                 freq.entry(parsed_b).and_modify(|x| *x += 1).or_insert(1);
             } else {
-                bail!("could not parse line")
+                return Err(ParseError::at(s, line, "could not parse line"));
             }
         }
 
@@ -71,12 +70,16 @@ impl Problem for HistorianHysteria {
     }
 }
 
+aoc_plumbing::register_problem!(HistorianHysteria);
+
 #[cfg(test)]
 mod tests {
     use aoc_plumbing::Solution;
 
     use super::*;
 
+    aoc_plumbing::answers_test!(HistorianHysteria);
+
     #[test]
     #[ignore]
     fn full_dataset() {
@@ -1,6 +1,6 @@
 use std::str::FromStr;
 
-use aoc_plumbing::Problem;
+use aoc_plumbing::{ParseError, ParseErrorContext, Problem};
 use rustc_hash::FxHashSet;
 
 #[derive(Debug, Clone, Default, Eq, PartialEq)]
@@ -9,12 +9,12 @@ struct Report {
 }
 
 impl FromStr for Report {
-    type Err = anyhow::Error;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let levels = s
             .split(' ')
-            .map(|x| x.parse::<i32>())
+            .map(|x| x.parse::<i32>().parse_err_at(s, x, "invalid level"))
             .collect::<Result<Vec<_>, _>>()?;
         Ok(Self { levels })
     }
@@ -109,12 +109,12 @@ pub struct RedNosedReports {
 }
 
 impl FromStr for RedNosedReports {
-    type Err = anyhow::Error;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let reports = s
             .lines()
-            .map(Report::from_str)
+            .map(|l| Report::from_str(l).map_err(|e| e.within(s, l)))
             .collect::<Result<Vec<_>, _>>()?;
         Ok(Self { reports })
     }
@@ -151,12 +151,16 @@ impl Problem for RedNosedReports {
     }
 }
 
+aoc_plumbing::register_problem!(RedNosedReports);
+
 #[cfg(test)]
 mod tests {
     use aoc_plumbing::Solution;
 
     use super::*;
 
+    aoc_plumbing::answers_test!(RedNosedReports);
+
     #[test]
     #[ignore]
     fn full_dataset() {
@@ -1,6 +1,6 @@
 use std::str::FromStr;
 
-use aoc_plumbing::Problem;
+use aoc_plumbing::{ParseErrorContext, Problem};
 use regex::Regex;
 
 #[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
@@ -31,9 +31,10 @@ impl FromStr for MullItOver {
             } else if &cap[0] == "don't()" {
                 enabled = false;
             } else {
+                let (a, b) = (&cap[2], &cap[3]);
                 instructions.push(Instruction {
-                    a: cap[2].parse::<i32>()?,
-                    b: cap[3].parse::<i32>()?,
+                    a: a.parse::<i32>().parse_err_at(s, a, "invalid operand")?,
+                    b: b.parse::<i32>().parse_err_at(s, b, "invalid operand")?,
                     enabled,
                 });
             }
@@ -75,12 +76,16 @@ impl Problem for MullItOver {
     }
 }
 
+aoc_plumbing::register_problem!(MullItOver);
+
 #[cfg(test)]
 mod tests {
     use aoc_plumbing::Solution;
 
     use super::*;
 
+    aoc_plumbing::answers_test!(MullItOver);
+
     #[test]
     #[ignore]
     fn full_dataset() {
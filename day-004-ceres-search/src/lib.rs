@@ -1,7 +1,11 @@
 use std::str::FromStr;
 
-use aoc_common::grid::{Coordinate, Grid};
-use aoc_plumbing::Problem;
+use aoc_common::{
+    direction::Compass,
+    grid::Grid,
+    pattern::{self, Orientation, Stencil},
+};
+use aoc_plumbing::{ParseError, Problem};
 
 #[derive(Debug, Clone)]
 pub struct CeresSearch {
@@ -9,87 +13,23 @@ pub struct CeresSearch {
 }
 
 impl FromStr for CeresSearch {
-    type Err = anyhow::Error;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         Ok(Self {
-            grid: Grid::from_str(s)?,
+            grid: Grid::from_str(s).map_err(|e| ParseError::at_line(s, e.row(), e.to_string()))?,
         })
     }
 }
 
 impl CeresSearch {
     fn total_xmas_occurences(&self) -> usize {
-        let mut ret = 0;
-
-        for i in 0..self.grid.n {
-            for j in 0..self.grid.m {
-                let coord = (i, j).into();
-                if self.grid[coord] == 'X' {
-                    ret += self.search_xmas_from(&coord);
-                }
-            }
-        }
-
-        ret
+        pattern::find_sequence(&self.grid, &['X', 'M', 'A', 'S'], &Compass::all()).len()
     }
 
     fn total_x_max_occurrences(&self) -> usize {
-        let mut ret = 0;
-
-        for i in 0..self.grid.n {
-            for j in 0..self.grid.m {
-                let coord = (i, j).into();
-                if self.grid[coord] == 'A' {
-                    ret += self.search_x_mas_from(&coord);
-                }
-            }
-        }
-
-        ret
-    }
-
-    fn search_xmas_from(&self, coord: &Coordinate) -> usize {
-        let i = coord.row();
-        let j = coord.col();
-
-        let candidates = [
-            [(i, j), (i - 1, j), (i - 2, j), (i - 3, j)],
-            [(i, j), (i + 1, j), (i + 2, j), (i + 3, j)],
-            [(i, j), (i, j - 1), (i, j - 2), (i, j - 3)],
-            [(i, j), (i, j + 1), (i, j + 2), (i, j + 3)],
-            [(i, j), (i - 1, j - 1), (i - 2, j - 2), (i - 3, j - 3)],
-            [(i, j), (i - 1, j + 1), (i - 2, j + 2), (i - 3, j + 3)],
-            [(i, j), (i + 1, j - 1), (i + 2, j - 2), (i + 3, j - 3)],
-            [(i, j), (i + 1, j + 1), (i + 2, j + 2), (i + 3, j + 3)],
-        ];
-
-        candidates
-            .iter()
-            .filter(|&x| {
-                x.map(|y| self.grid.get(y.into())) == [Some('X'), Some('M'), Some('A'), Some('S')]
-            })
-            .count()
-    }
-
-    fn search_x_mas_from(&self, coord: &Coordinate) -> usize {
-        let i = coord.row();
-        let j = coord.col();
-
-        let candidates = [
-            [(i - 1, j - 1), (i, j), (i + 1, j + 1)],
-            [(i - 1, j + 1), (i, j), (i + 1, j - 1)],
-        ];
-
-        if candidates.iter().all(|x| {
-            let sequence = x.map(|y| self.grid.get(y.into()));
-            sequence == [Some('M'), Some('A'), Some('S')]
-                || sequence == [Some('S'), Some('A'), Some('M')]
-        }) {
-            return 1;
-        }
-
-        0
+        let x_mas = Stencil::parse("M.M\n.A.\nS.S", '.');
+        pattern::find_stencil(&self.grid, &x_mas, &Orientation::rotations()).len()
     }
 }
 
@@ -111,12 +51,16 @@ impl Problem for CeresSearch {
     }
 }
 
+aoc_plumbing::register_problem!(CeresSearch);
+
 #[cfg(test)]
 mod tests {
     use aoc_plumbing::Solution;
 
     use super::*;
 
+    aoc_plumbing::answers_test!(CeresSearch);
+
     #[test]
     #[ignore]
     fn full_dataset() {
@@ -1,7 +1,6 @@
 use std::{cmp::Ordering, str::FromStr};
 
-use anyhow::anyhow;
-use aoc_plumbing::Problem;
+use aoc_plumbing::{ParseError, ParseErrorContext, Problem};
 use rustc_hash::FxHashSet;
 
 #[derive(Debug, Clone)]
@@ -11,32 +10,33 @@ pub struct PrintQueue {
 }
 
 impl FromStr for PrintQueue {
-    type Err = anyhow::Error;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (part1, part2) = s
-            .split_once("\n\n")
-            .ok_or_else(|| anyhow!("could not parse input"))?;
+        let (part1, part2) = s.split_once("\n\n").ok_or_else(|| {
+            ParseError::at_end(s, "expected a blank line between the rules and updates")
+        })?;
 
         let rules = part1
             .trim()
             .lines()
             .map(|l| {
-                l.split_once('|')
-                    .and_then(|(a, b)| match (a.parse::<i32>(), b.parse::<i32>()) {
-                        (Ok(parsed_a), Ok(parsed_b)) => Some((parsed_a, parsed_b)),
-                        _ => None,
-                    })
-                    .ok_or_else(|| anyhow!("could not parse rule"))
+                let (a, b) = l
+                    .split_once('|')
+                    .parse_err_at(s, l, "could not parse rule")?;
+                Ok((
+                    a.parse::<i32>().parse_err_at(s, a, "invalid page number")?,
+                    b.parse::<i32>().parse_err_at(s, b, "invalid page number")?,
+                ))
             })
-            .collect::<Result<FxHashSet<_>, _>>()?;
+            .collect::<Result<FxHashSet<_>, ParseError>>()?;
 
         let mut updates = part2
             .trim()
             .lines()
             .map(|l| {
                 l.split(',')
-                    .map(|x| x.parse::<i32>())
+                    .map(|x| x.parse::<i32>().parse_err_at(s, x, "invalid page number"))
                     .collect::<Result<Vec<_>, _>>()
             })
             .collect::<Result<Vec<_>, _>>()?;
@@ -98,12 +98,16 @@ impl Problem for PrintQueue {
     }
 }
 
+aoc_plumbing::register_problem!(PrintQueue);
+
 #[cfg(test)]
 mod tests {
     use aoc_plumbing::Solution;
 
     use super::*;
 
+    aoc_plumbing::answers_test!(PrintQueue);
+
     #[test]
     #[ignore]
     fn full_dataset() {
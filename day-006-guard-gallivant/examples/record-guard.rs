@@ -0,0 +1,57 @@
+//! Records the guard's walk from part one.
+//!
+//! ```text
+//! cargo run --release -p guard-gallivant --features record --example record-guard -- <input> [output]
+//! ```
+//!
+//! The output is written as an animated GIF if it ends in `.gif`, or as a
+//! directory of PNG frames otherwise. Without an output, the walk is played
+//! back in the terminal.
+use std::{str::FromStr, time::Duration};
+
+use aoc_common::{
+    record::{FrameRecorder, GifRecorder, Recording, TerminalPlayback},
+    render::Rgb,
+};
+use aoc_plumbing::Problem;
+use guard_gallivant::GuardGallivant;
+
+fn colour(c: &char) -> Rgb {
+    match c {
+        '#' => Rgb(90, 90, 90),
+        'X' => Rgb(40, 90, 160),
+        '.' => Rgb::BLACK,
+        _ => Rgb(230, 60, 50),
+    }
+}
+
+fn main() -> anyhow::Result<()> {
+    let mut args = std::env::args().skip(1);
+    let input_path = args
+        .next()
+        .ok_or_else(|| anyhow::anyhow!("usage: record-guard <input> [output]"))?;
+    let input = std::fs::read_to_string(input_path)?;
+
+    let recording = match args.next() {
+        Some(out) if out.ends_with(".gif") => Recording::new(
+            GifRecorder::new(out, colour)
+                .scale(4)
+                .every(20)
+                .delay(Duration::from_millis(20)),
+        ),
+        Some(out) => Recording::new(FrameRecorder::png(out, colour).scale(4).every(20)),
+        None => Recording::new(
+            TerminalPlayback::new(colour)
+                .every(10)
+                .delay(Duration::from_millis(20)),
+        ),
+    };
+
+    let mut problem = GuardGallivant::from_str(&input)?;
+    problem.record(recording.clone());
+    let visited = problem.part_one()?;
+    recording.finish()?;
+
+    println!("visited {} positions", visited);
+    Ok(())
+}
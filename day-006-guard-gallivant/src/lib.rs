@@ -1,11 +1,15 @@
 use std::fmt;
 use std::str::FromStr;
 
+use anyhow::bail;
 use aoc_common::{
     direction::Cardinal,
     grid::{Coordinate, Grid},
 };
-use aoc_plumbing::Problem;
+use aoc_plumbing::{ParseError, ParseErrorContext, Problem};
+
+#[cfg(feature = "record")]
+use aoc_common::record::Recording;
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
 enum Tile {
@@ -19,7 +23,8 @@ impl TryFrom<char> for Tile {
     fn try_from(value: char) -> Result<Self, Self::Error> {
         match value {
             '#' => Ok(Self::Obstacle),
-            _ => Ok(Self::Empty),
+            '.' | '<' | '>' | '^' | 'v' => Ok(Self::Empty),
+            _ => bail!("unknown tile {:?}", value),
         }
     }
 }
@@ -38,54 +43,67 @@ pub struct GuardGallivant {
     grid: Grid<Tile>,
     start_pos: Coordinate,
     start_dir: Cardinal,
+    #[cfg(feature = "record")]
+    recording: Recording<char>,
 }
 
 impl FromStr for GuardGallivant {
-    type Err = anyhow::Error;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let mut grid = Vec::default();
-        let mut start_pos = Coordinate::default();
-        let mut start_dir = Cardinal::North;
+        let mut start = None;
 
         for (i, line) in s.lines().enumerate() {
             let mut row = Vec::default();
 
-            for (j, c) in line.chars().enumerate() {
+            for (j, (offset, c)) in line.char_indices().enumerate() {
                 match c {
-                    '<' => {
-                        start_pos = (i, j).into();
-                        start_dir = Cardinal::West;
-                    }
-                    '>' => {
-                        start_pos = (i, j).into();
-                        start_dir = Cardinal::East;
-                    }
-                    '^' => {
-                        start_pos = (i, j).into();
-                        start_dir = Cardinal::North;
-                    }
-                    'v' => {
-                        start_pos = (i, j).into();
-                        start_dir = Cardinal::South;
-                    }
+                    '<' => start = Some(((i, j).into(), Cardinal::West)),
+                    '>' => start = Some(((i, j).into(), Cardinal::East)),
+                    '^' => start = Some(((i, j).into(), Cardinal::North)),
+                    'v' => start = Some(((i, j).into(), Cardinal::South)),
                     _ => (),
                 };
 
-                row.push(Tile::try_from(c)?);
+                let fragment = &line[offset..offset + c.len_utf8()];
+                row.push(Tile::try_from(c).parse_err_at(s, fragment, "invalid tile")?);
             }
 
             grid.push(row);
         }
+
+        let (start_pos, start_dir) = start.parse_err_at(s, s, "no guard found")?;
+
         Ok(Self {
-            grid: grid.into(),
+            grid: Grid::try_from_rows(grid)
+                .map_err(|e| ParseError::at_line(s, e.row(), e.to_string()))?,
             start_pos,
             start_dir,
+            #[cfg(feature = "record")]
+            recording: Recording::default(),
         })
     }
 }
 
 impl GuardGallivant {
+    /// Records the guard's walk in part one, with visited positions marked
+    /// with `X`.
+    #[cfg(feature = "record")]
+    pub fn record(&mut self, recording: Recording<char>) {
+        self.recording = recording;
+    }
+
+    #[cfg(feature = "record")]
+    fn guard(dir: &Cardinal) -> char {
+        match dir {
+            Cardinal::North => '^',
+            Cardinal::East => '>',
+            Cardinal::South => 'v',
+            Cardinal::West => '<',
+        }
+    }
+
     fn visited_positions(&self) -> usize {
         let mut cur_pos = self.start_pos;
         let mut cur_dir = self.start_dir;
@@ -93,6 +111,16 @@ impl GuardGallivant {
         let mut visited = Grid::new(self.grid.n, self.grid.m, false);
         let mut ret = 0;
 
+        #[cfg(feature = "record")]
+        if self.recording.is_active() {
+            let mut frame = self.grid.map_cells(|t| match t {
+                Tile::Obstacle => ['#'],
+                Tile::Empty => ['.'],
+            });
+            frame[cur_pos] = Self::guard(&cur_dir);
+            self.recording.frame(&frame);
+        }
+
         while self.grid.is_in_bounds(cur_pos) {
             if !visited[cur_pos] {
                 ret += 1;
@@ -103,7 +131,14 @@ impl GuardGallivant {
                 cur_dir = cur_dir.right();
             }
 
+            #[cfg(feature = "record")]
+            let prev_pos = cur_pos;
+
             cur_pos = cur_pos.neighbour(&cur_dir);
+
+            #[cfg(feature = "record")]
+            self.recording
+                .diff(&[(prev_pos, 'X'), (cur_pos, Self::guard(&cur_dir))]);
         }
 
         ret
@@ -183,12 +218,16 @@ impl Problem for GuardGallivant {
     }
 }
 
+aoc_plumbing::register_problem!(GuardGallivant);
+
 #[cfg(test)]
 mod tests {
     use aoc_plumbing::Solution;
 
     use super::*;
 
+    aoc_plumbing::answers_test!(GuardGallivant);
+
     #[test]
     #[ignore]
     fn full_dataset() {
@@ -203,4 +242,14 @@ mod tests {
         let solution = GuardGallivant::solve(&input).unwrap();
         assert_eq!(solution, Solution::new(41, 6));
     }
+
+    #[test]
+    fn invalid_input() {
+        let err = GuardGallivant::from_str("..#\n...").unwrap_err();
+        assert_eq!(err.message, "no guard found");
+        assert!(GuardGallivant::from_str("").is_err());
+
+        let err = GuardGallivant::from_str("..#\n.^\n...").unwrap_err();
+        assert_eq!(err.line, 2);
+    }
 }
@@ -1,7 +1,6 @@
 use std::str::FromStr;
 
-use anyhow::anyhow;
-use aoc_plumbing::Problem;
+use aoc_plumbing::{ParseError, ParseErrorContext, Problem};
 use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
 
 #[derive(Debug, Clone, Default, Eq, PartialEq)]
@@ -11,17 +10,15 @@ struct Equation {
 }
 
 impl FromStr for Equation {
-    type Err = anyhow::Error;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (part1, part2) = s
-            .split_once(": ")
-            .ok_or_else(|| anyhow!("invalid equation"))?;
+        let (part1, part2) = s.split_once(": ").parse_err_at(s, s, "invalid equation")?;
 
-        let value = part1.parse()?;
+        let value = part1.parse().parse_err_at(s, part1, "invalid test value")?;
         let terms = part2
             .split(' ')
-            .map(|x| x.parse())
+            .map(|x| x.parse().parse_err_at(s, x, "invalid term"))
             .collect::<Result<Vec<_>, _>>()?;
 
         Ok(Self { value, terms })
@@ -73,12 +70,12 @@ pub struct BridgeRepair {
 }
 
 impl FromStr for BridgeRepair {
-    type Err = anyhow::Error;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let equations = s
             .lines()
-            .map(Equation::from_str)
+            .map(|l| Equation::from_str(l).map_err(|e| e.within(s, l)))
             .collect::<Result<Vec<_>, _>>()?;
         Ok(Self { equations })
     }
@@ -120,12 +117,16 @@ impl Problem for BridgeRepair {
     }
 }
 
+aoc_plumbing::register_problem!(BridgeRepair);
+
 #[cfg(test)]
 mod tests {
     use aoc_plumbing::Solution;
 
     use super::*;
 
+    aoc_plumbing::answers_test!(BridgeRepair);
+
     #[test]
     #[ignore]
     fn full_dataset() {
@@ -1,8 +1,7 @@
 use std::str::FromStr;
 
-use anyhow::anyhow;
 use aoc_common::grid::{Coordinate, Grid};
-use aoc_plumbing::Problem;
+use aoc_plumbing::{ParseError, Problem};
 use itertools::Itertools;
 use rustc_hash::{FxHashMap, FxHashSet};
 
@@ -13,7 +12,7 @@ pub struct ResonantCollinearity {
 }
 
 impl FromStr for ResonantCollinearity {
-    type Err = anyhow::Error;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let mut antennas: FxHashMap<char, FxHashSet<Coordinate>> = FxHashMap::default();
@@ -21,7 +20,7 @@ impl FromStr for ResonantCollinearity {
         let m = s
             .lines()
             .next()
-            .ok_or_else(|| anyhow!("could not parse empty input"))?
+            .ok_or_else(|| ParseError::at_end(s, "could not parse empty input"))?
             .len();
 
         for (i, line) in s.lines().enumerate() {
@@ -106,12 +105,16 @@ impl Problem for ResonantCollinearity {
     }
 }
 
+aoc_plumbing::register_problem!(ResonantCollinearity);
+
 #[cfg(test)]
 mod tests {
     use aoc_plumbing::Solution;
 
     use super::*;
 
+    aoc_plumbing::answers_test!(ResonantCollinearity);
+
     #[test]
     #[ignore]
     fn full_dataset() {
@@ -1,9 +1,7 @@
-use std::cmp::Reverse;
-use std::{collections::BinaryHeap, str::FromStr};
+use std::str::FromStr;
 
-use aoc_common::interval::Interval;
-use aoc_plumbing::Problem;
-use rustc_hash::FxHashMap;
+use aoc_common::interval::{Allocator, Interval};
+use aoc_plumbing::{ParseError, ParseErrorContext, Problem};
 
 #[derive(Debug, Clone)]
 pub struct DiskFragmenter {
@@ -12,24 +10,29 @@ pub struct DiskFragmenter {
 }
 
 impl FromStr for DiskFragmenter {
-    type Err = anyhow::Error;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let mut cur = 0;
-        let mut iter = s.chars();
+        let digits = s.trim_end();
+        let mut iter = digits.char_indices().map(|(i, c)| {
+            c.to_digit(10).map(|d| d as isize).parse_err_at(
+                s,
+                &digits[i..i + c.len_utf8()],
+                "invalid digit",
+            )
+        });
         let mut file_blocks = Vec::default();
         let mut free_blocks = Vec::default();
 
         while let (Some(a), b) = (iter.next(), iter.next()) {
-            match a.to_digit(10).map(|d| d as isize) {
-                Some(x) if x.is_positive() => {
-                    file_blocks.push(Interval::new(cur, cur + x));
-                    cur += x;
-                }
-                _ => (),
+            let x = a?;
+            if x.is_positive() {
+                file_blocks.push(Interval::new(cur, cur + x));
+                cur += x;
             }
 
-            match b.and_then(|d| d.to_digit(10)).map(|d| d as isize) {
+            match b.transpose()? {
                 Some(x) if x.is_positive() => {
                     free_blocks.push(Interval::new(cur, cur + x));
                     cur += x;
@@ -47,94 +50,61 @@ impl FromStr for DiskFragmenter {
 
 impl DiskFragmenter {
     fn part1(&self) -> i64 {
+        // the blocks of each file that have not been moved yet
+        let mut files = self.file_blocks.clone();
         let mut ret = 0;
 
-        let mut i = 0;
-        let mut j = 0;
-        let mut k = self.file_blocks.len() - 1;
-        let mut cur_file = self.file_blocks[i];
-        let mut cur_free = self.free_blocks[j];
-        let mut cur_candidate = self.file_blocks[k];
-
-        while cur_free.less_than(cur_candidate.start) {
-            if cur_file.less_than(cur_free.start) {
-                ret += i as isize * cur_file.sum();
-                i += 1;
-                cur_file = self.file_blocks[i];
-            } else if cur_candidate.len() == cur_free.len() {
-                ret += k as isize * cur_free.sum();
-                j += 1;
-                k -= 1;
-                cur_free = self.free_blocks[j];
-                cur_candidate = self.file_blocks[k];
-            } else if cur_candidate.len() < cur_free.len() {
-                let (left, right) =
-                    cur_free.split_unchecked(cur_free.start + cur_candidate.len() as isize);
-                ret += k as isize * left.sum();
-                k -= 1;
-                cur_free = right;
-                cur_candidate = self.file_blocks[k];
-            } else {
-                ret += k as isize * cur_free.sum();
-                cur_candidate.end -= cur_free.len() as isize;
-                j += 1;
-                cur_free = self.free_blocks[j];
-            }
-        }
-
-        if i == k {
-            ret += k as isize * cur_candidate.sum();
-        }
-
-        ret as i64
-    }
+        // files[..k] may still have blocks to move
+        let mut k = files.len();
 
-    fn part2(&self) -> Result<i64, anyhow::Error> {
-        let mut spans: FxHashMap<usize, BinaryHeap<Reverse<(isize, isize)>>> = FxHashMap::default();
+        'free: for free in self.free_blocks.iter() {
+            let mut free = *free;
 
-        for block in &self.free_blocks {
-            spans
-                .entry(block.len())
-                .and_modify(|x| x.push(Reverse((block.start, block.end))))
-                .or_insert(BinaryHeap::from([Reverse((block.start, block.end))]));
-        }
-
-        let mut ret = 0;
-
-        for (k, cur_candidate) in self.file_blocks.iter().enumerate().rev() {
-            if let Some((min_k, _)) = spans
-                .iter()
-                .filter(|&(k, v)| *k >= cur_candidate.len() && !v.is_empty())
-                .min_by_key(|&(_, v)| v.peek().map(|x| x.0).unwrap_or_default())
-            {
-                // safe to unwrap because we guarantee that this entry exists above
-                let block = Interval::from(spans[min_k].peek().unwrap().0);
-                if cur_candidate.less_than(block.start) {
-                    ret += k as isize * cur_candidate.sum();
-                    continue;
+            while !free.is_empty() {
+                while k > 0 && files[k - 1].is_empty() {
+                    k -= 1;
                 }
 
-                let (left, right) =
-                    block.split_unchecked(block.start + cur_candidate.len() as isize);
-
-                ret += k as isize * left.sum();
+                let Some(file) = k.checked_sub(1).map(|i| &mut files[i]) else {
+                    break 'free;
+                };
 
-                spans.entry(*min_k).and_modify(|x| {
-                    x.pop();
-                });
-
-                if !right.is_empty() {
-                    spans
-                        .entry(right.len())
-                        .and_modify(|x| x.push(Reverse((right.start, right.end))))
-                        .or_insert(BinaryHeap::from([Reverse((right.start, right.end))]));
+                // once the last file is left of the free space, nothing else
+                // can move
+                if file.start < free.start {
+                    break 'free;
                 }
-            } else {
-                ret += k as isize * cur_candidate.sum();
+
+                let n = file.len().min(free.len()) as isize;
+                ret += (k - 1) as isize * Interval::new(free.start, free.start + n).sum();
+                file.end -= n;
+                free.start += n;
             }
         }
 
-        Ok(ret as i64)
+        ret += files
+            .iter()
+            .enumerate()
+            .map(|(i, file)| i as isize * file.sum())
+            .sum::<isize>();
+
+        ret as i64
+    }
+
+    fn part2(&self) -> i64 {
+        let mut free = Allocator::new(self.free_blocks.iter().copied());
+
+        // the space a file leaves behind is to the right of every file still
+        // to be moved, so it never needs to be released
+        self.file_blocks
+            .iter()
+            .enumerate()
+            .rev()
+            .map(|(k, file)| {
+                let moved = free.allocate(file.len(), file.start).unwrap_or(*file);
+                k as i64 * moved.sum() as i64
+            })
+            .sum()
     }
 }
 
@@ -152,16 +122,20 @@ impl Problem for DiskFragmenter {
     }
 
     fn part_two(&mut self) -> Result<Self::P2, Self::ProblemError> {
-        self.part2()
+        Ok(self.part2())
     }
 }
 
+aoc_plumbing::register_problem!(DiskFragmenter);
+
 #[cfg(test)]
 mod tests {
     use aoc_plumbing::Solution;
 
     use super::*;
 
+    aoc_plumbing::answers_test!(DiskFragmenter);
+
     #[test]
     #[ignore]
     fn full_dataset() {
@@ -176,4 +150,17 @@ mod tests {
         let solution = DiskFragmenter::solve(&input).unwrap();
         assert_eq!(solution, Solution::new(1928, 2858));
     }
+
+    #[test]
+    fn degenerate_disks() {
+        assert_eq!(DiskFragmenter::solve("").unwrap(), Solution::new(0, 0));
+        assert_eq!(DiskFragmenter::solve("1").unwrap(), Solution::new(0, 0));
+        assert_eq!(DiskFragmenter::solve("5").unwrap(), Solution::new(0, 0));
+        assert_eq!(
+            DiskFragmenter::solve("1020304").unwrap(),
+            Solution::new(117, 117)
+        );
+        assert_eq!(DiskFragmenter::solve("15").unwrap(), Solution::new(0, 0));
+        assert_eq!(DiskFragmenter::solve("151").unwrap(), Solution::new(1, 1));
+    }
 }
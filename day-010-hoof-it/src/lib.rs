@@ -1,8 +1,10 @@
 use std::str::FromStr;
 
-use anyhow::anyhow;
-use aoc_common::grid::{Coordinate, Grid};
-use aoc_plumbing::Problem;
+use aoc_common::{
+    grid::{Coordinate, Grid},
+    pathfinding,
+};
+use aoc_plumbing::{ParseError, ParseErrorContext, Problem};
 use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
 
 #[derive(Debug, Clone)]
@@ -12,7 +14,7 @@ pub struct HoofIt {
 }
 
 impl FromStr for HoofIt {
-    type Err = anyhow::Error;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let mut cells = Vec::default();
@@ -21,11 +23,12 @@ impl FromStr for HoofIt {
         for (i, line) in s.lines().enumerate() {
             let mut row = Vec::default();
 
-            for (j, c) in line.chars().enumerate() {
-                let d = c
-                    .to_digit(10)
-                    .map(|x| x as u8)
-                    .ok_or_else(|| anyhow!("invalid cell"))?;
+            for (j, (offset, c)) in line.char_indices().enumerate() {
+                let d = c.to_digit(10).map(|x| x as u8).parse_err_at(
+                    s,
+                    &line[offset..offset + c.len_utf8()],
+                    "invalid cell",
+                )?;
 
                 if d == 0 {
                     trailheads.push((i, j).into());
@@ -38,7 +41,8 @@ impl FromStr for HoofIt {
         }
 
         Ok(Self {
-            grid: Grid::from(cells),
+            grid: Grid::try_from_rows(cells)
+                .map_err(|e| ParseError::at_line(s, e.row(), e.to_string()))?,
             trailheads,
         })
     }
@@ -48,63 +52,24 @@ impl HoofIt {
     fn total_trail_score(&self) -> usize {
         self.trailheads
             .par_iter()
-            .map(|x| {
-                self.total_trail_score_helper(x, &mut Grid::new(self.grid.n, self.grid.m, false))
-            })
-            .sum()
-    }
-
-    fn total_trail_score_helper(&self, head: &Coordinate, visited: &mut Grid<bool>) -> usize {
-        if visited[*head] {
-            return 0;
-        }
-
-        visited[*head] = true;
-
-        let cell = self.grid[*head];
-        if cell == 9 {
-            return 1;
-        }
-
-        head.cardinal_neighbours()
-            .iter()
-            .filter(|&x| self.grid.is_in_bounds(*x) && self.grid[*x] == cell + 1)
-            .map(|x| self.total_trail_score_helper(x, visited))
+            .map(|x| pathfinding::dfs(x, |c| self.uphill(c), |c| self.grid[*c] == 9).len())
             .sum()
     }
 
     fn total_trail_rating(&self) -> usize {
-        let mut memo = Grid::new(self.grid.n, self.grid.m, None);
-
         self.trailheads
             .iter()
-            .map(|x| self.total_trail_rating_helper(x, &mut memo))
+            .map(|x| pathfinding::count_paths(x, |c| self.uphill(c), |c| self.grid[*c] == 9))
             .sum()
     }
 
-    fn total_trail_rating_helper(
-        &self,
-        head: &Coordinate,
-        memo: &mut Grid<Option<usize>>,
-    ) -> usize {
-        let cell = self.grid[*head];
-        if cell == 9 {
-            memo[*head] = Some(1)
-        }
-
-        if let Some(x) = memo[*head] {
-            return x;
-        }
-
-        let result: usize = head
-            .cardinal_neighbours()
-            .iter()
-            .filter(|&x| self.grid.is_in_bounds(*x) && self.grid[*x] == cell + 1)
-            .map(|x| self.total_trail_rating_helper(x, memo))
-            .sum();
-
-        memo[*head] = Some(result);
-        result
+    /// The neighbours of `coord` that are exactly one higher than it.
+    fn uphill(&self, coord: &Coordinate) -> impl Iterator<Item = Coordinate> + '_ {
+        let next = self.grid[*coord] + 1;
+        self.grid
+            .cardinal_neighbours(*coord)
+            .filter(move |(_, h)| **h == next)
+            .map(|(x, _)| x)
     }
 }
 
@@ -126,12 +91,16 @@ impl Problem for HoofIt {
     }
 }
 
+aoc_plumbing::register_problem!(HoofIt);
+
 #[cfg(test)]
 mod tests {
     use aoc_plumbing::Solution;
 
     use super::*;
 
+    aoc_plumbing::answers_test!(HoofIt);
+
     #[test]
     #[ignore]
     fn full_dataset() {
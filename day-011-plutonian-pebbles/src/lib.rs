@@ -1,6 +1,6 @@
 use std::str::FromStr;
 
-use aoc_plumbing::Problem;
+use aoc_plumbing::{ParseError, ParseErrorContext, Problem};
 use rustc_hash::FxHashMap;
 
 #[derive(Debug, Clone)]
@@ -9,13 +9,13 @@ pub struct PlutonianPebbles {
 }
 
 impl FromStr for PlutonianPebbles {
-    type Err = anyhow::Error;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         Ok(Self {
             pebbles: s
                 .split_whitespace()
-                .map(|x| x.parse())
+                .map(|x| x.parse().parse_err_at(s, x, "invalid pebble"))
                 .collect::<Result<Vec<_>, _>>()?,
         })
     }
@@ -52,7 +52,7 @@ impl PlutonianPebbles {
         } else {
             let digits = pebble.ilog10() + 1;
 
-            if digits % 2 == 0 {
+            if digits.is_multiple_of(2) {
                 let divisor = 10_u64.pow(digits / 2);
                 Self::blink(pebble / divisor, iterations - 1, memo)
                     + Self::blink(pebble % divisor, iterations - 1, memo)
@@ -88,12 +88,16 @@ impl Problem for PlutonianPebbles {
     }
 }
 
+aoc_plumbing::register_problem!(PlutonianPebbles);
+
 #[cfg(test)]
 mod tests {
     use aoc_plumbing::Solution;
 
     use super::*;
 
+    aoc_plumbing::answers_test!(PlutonianPebbles);
+
     #[test]
     #[ignore]
     fn full_dataset() {
@@ -1,156 +1,33 @@
 use std::str::FromStr;
 
-use aoc_common::grid::{Coordinate, Grid};
-use aoc_plumbing::Problem;
+use aoc_common::{grid::Grid, region::Regions};
+use aoc_plumbing::{ParseError, Problem};
 
 #[derive(Debug, Clone)]
 pub struct GardenGroups {
-    grid: Grid<char>,
+    regions: Regions,
 }
 
 impl FromStr for GardenGroups {
-    type Err = anyhow::Error;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let grid = Grid::<char>::from_str(s)
+            .map_err(|e| ParseError::at_line(s, e.row(), e.to_string()))?;
+
         Ok(Self {
-            grid: Grid::from_str(s)?,
+            regions: Regions::new(&grid),
         })
     }
 }
 
 impl GardenGroups {
     fn total_price_regular(&self) -> usize {
-        let mut visited = Grid::new(self.grid.n, self.grid.m, false);
-        let mut ret = 0;
-
-        for i in 0..self.grid.n {
-            for j in 0..self.grid.m {
-                let coord = (i, j).into();
-
-                if visited[coord] {
-                    continue;
-                }
-
-                let plant = self.grid[coord];
-                let (area, perimeter) =
-                    self.total_price_regular_helper(&coord, plant, &mut visited);
-                ret += area * perimeter;
-            }
-        }
-
-        ret
-    }
-
-    fn total_price_regular_helper(
-        &self,
-        coord: &Coordinate,
-        plant: char,
-        visited: &mut Grid<bool>,
-    ) -> (usize, usize) {
-        if visited[*coord] {
-            return (0, 0);
-        }
-
-        visited[*coord] = true;
-
-        let mut area = 1;
-        let mut perimeter = 4;
-        for n in coord.cardinal_neighbours() {
-            if self.grid.is_in_bounds(n) && self.grid[n] == plant {
-                let result = self.total_price_regular_helper(&n, plant, visited);
-                area += result.0;
-                perimeter = perimeter + result.1 - 1;
-            }
-        }
-
-        (area, perimeter)
+        self.regions.iter().map(|r| r.area() * r.perimeter()).sum()
     }
 
     fn total_price_discount(&self) -> usize {
-        let mut visited = Grid::new(self.grid.n, self.grid.m, false);
-        let mut ret = 0;
-
-        for i in 0..self.grid.n {
-            for j in 0..self.grid.m {
-                let coord = (i, j).into();
-
-                if visited[coord] {
-                    continue;
-                }
-
-                let plant = self.grid[coord];
-                let (area, turns) = self.total_price_discount_helper(&coord, plant, &mut visited);
-                ret += area * turns;
-            }
-        }
-
-        ret
-    }
-
-    fn total_price_discount_helper(
-        &self,
-        coord: &Coordinate,
-        plant: char,
-        visited: &mut Grid<bool>,
-    ) -> (usize, usize) {
-        if visited[*coord] {
-            return (0, 0);
-        }
-
-        visited[*coord] = true;
-
-        let mut area = 1;
-        let mut turns = 0;
-
-        let (mut a, mut b) = (
-            self.grid.get(coord.north()).unwrap_or('.'),
-            self.grid.get(coord.east()).unwrap_or('.'),
-        );
-        if (a != plant && b != plant)
-            || (a == plant && b == plant && self.grid[coord.northeast()] != plant)
-        {
-            turns += 1;
-        }
-
-        (a, b) = (
-            self.grid.get(coord.east()).unwrap_or('.'),
-            self.grid.get(coord.south()).unwrap_or('.'),
-        );
-        if (a != plant && b != plant)
-            || (a == plant && b == plant && self.grid[coord.southeast()] != plant)
-        {
-            turns += 1;
-        }
-
-        (a, b) = (
-            self.grid.get(coord.south()).unwrap_or('.'),
-            self.grid.get(coord.west()).unwrap_or('.'),
-        );
-        if (a != plant && b != plant)
-            || (a == plant && b == plant && self.grid[coord.southwest()] != plant)
-        {
-            turns += 1;
-        }
-
-        (a, b) = (
-            self.grid.get(coord.west()).unwrap_or('.'),
-            self.grid.get(coord.north()).unwrap_or('.'),
-        );
-        if (a != plant && b != plant)
-            || (a == plant && b == plant && self.grid[coord.northwest()] != plant)
-        {
-            turns += 1;
-        }
-
-        for n in coord.cardinal_neighbours() {
-            if self.grid.is_in_bounds(n) && self.grid[n] == plant {
-                let result = self.total_price_discount_helper(&n, plant, visited);
-                area += result.0;
-                turns += result.1;
-            }
-        }
-
-        (area, turns)
+        self.regions.iter().map(|r| r.area() * r.sides()).sum()
     }
 }
 
@@ -172,12 +49,16 @@ impl Problem for GardenGroups {
     }
 }
 
+aoc_plumbing::register_problem!(GardenGroups);
+
 #[cfg(test)]
 mod tests {
     use aoc_plumbing::Solution;
 
     use super::*;
 
+    aoc_plumbing::answers_test!(GardenGroups);
+
     #[test]
     #[ignore]
     fn full_dataset() {
@@ -1,7 +1,6 @@
 use std::str::FromStr;
 
-use anyhow::anyhow;
-use aoc_plumbing::Problem;
+use aoc_plumbing::{ParseError, ParseErrorContext, Problem};
 
 #[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
 struct Machine {
@@ -35,44 +34,54 @@ impl Machine {
 
         a * 3 + b
     }
+
+    /// Parses a line of the form `<prefix>X<sep><x>, Y<sep><y>`, where `line`
+    /// is a subslice of `input`.
+    fn parse_pair(
+        input: &str,
+        line: &str,
+        prefix: &str,
+        sep: char,
+    ) -> Result<(i64, i64), ParseError> {
+        let rest = line.strip_prefix(prefix).parse_err_at(
+            input,
+            line,
+            &format!("expected {:?}", prefix),
+        )?;
+        let (x, y) = rest
+            .split_once(", ")
+            .parse_err_at(input, rest, "expected two coordinates")?;
+
+        let x = x
+            .strip_prefix('X')
+            .and_then(|v| v.strip_prefix(sep))
+            .parse_err_at(input, x, &format!("expected \"X{}\"", sep))?;
+        let y = y
+            .strip_prefix('Y')
+            .and_then(|v| v.strip_prefix(sep))
+            .parse_err_at(input, y, &format!("expected \"Y{}\"", sep))?;
+
+        Ok((
+            x.parse().parse_err_at(input, x, "invalid X value")?,
+            y.parse().parse_err_at(input, y, "invalid Y value")?,
+        ))
+    }
 }
 
 impl FromStr for Machine {
-    type Err = anyhow::Error;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let mut lines = s.lines();
+        let mut next_line = || {
+            lines
+                .next()
+                .ok_or_else(|| ParseError::at_end(s, "unexpected end of machine"))
+        };
 
-        let (x1, y1) = lines
-            .next()
-            .and_then(|l| {
-                l[12..14]
-                    .parse::<i64>()
-                    .and_then(|x| l[18..20].parse::<i64>().map(|y| (x, y)))
-                    .ok()
-            })
-            .ok_or_else(|| anyhow!("unexpected button A input"))?;
-
-        let (x2, y2) = lines
-            .next()
-            .and_then(|l| {
-                l[12..14]
-                    .parse::<i64>()
-                    .and_then(|x| l[18..20].parse::<i64>().map(|y| (x, y)))
-                    .ok()
-            })
-            .ok_or_else(|| anyhow!("unexpected button B input"))?;
-
-        let (x, y) = lines
-            .next()
-            .map(|l| l.split_whitespace().collect::<Vec<_>>())
-            .and_then(|v| {
-                v[1][2..v[1].len() - 1]
-                    .parse::<i64>()
-                    .and_then(|x| v[2][2..v[2].len()].parse::<i64>().map(|y| (x, y)))
-                    .ok()
-            })
-            .ok_or_else(|| anyhow!("unexpected prize input"))?;
+        let (x1, y1) = Self::parse_pair(s, next_line()?, "Button A: ", '+')?;
+        let (x2, y2) = Self::parse_pair(s, next_line()?, "Button B: ", '+')?;
+        let (x, y) = Self::parse_pair(s, next_line()?, "Prize: ", '=')?;
 
         Ok(Self {
             x,
@@ -91,12 +100,12 @@ pub struct ClawContraption {
 }
 
 impl FromStr for ClawContraption {
-    type Err = anyhow::Error;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let machines = s
             .split("\n\n")
-            .map(Machine::from_str)
+            .map(|m| Machine::from_str(m).map_err(|e| e.within(s, m)))
             .collect::<Result<Vec<_>, _>>()?;
 
         Ok(Self { machines })
@@ -125,12 +134,16 @@ impl Problem for ClawContraption {
     }
 }
 
+aoc_plumbing::register_problem!(ClawContraption);
+
 #[cfg(test)]
 mod tests {
     use aoc_plumbing::Solution;
 
     use super::*;
 
+    aoc_plumbing::answers_test!(ClawContraption);
+
     #[test]
     #[ignore]
     fn full_dataset() {
@@ -1,51 +1,49 @@
 use std::str::FromStr;
 
-use anyhow::{anyhow, bail, Ok};
-use aoc_common::algebra::{Point3, Vector3};
-use aoc_plumbing::Problem;
+use anyhow::bail;
+use aoc_common::algebra::{Point2, Vector2};
+use aoc_plumbing::{ParseError, ParseErrorContext, Problem};
 
 #[derive(Debug, Clone, Copy, Default, PartialEq)]
 struct Robot {
-    position: Point3<i64>,
-    velocity: Vector3<i64>,
+    position: Point2<i64>,
+    velocity: Vector2<i64>,
 }
 
 impl Robot {
-    fn simulate(&self, width: i64, height: i64, seconds: i64) -> Point3<i64> {
-        let mut ret = self.position + self.velocity * seconds;
-        ret.x = ret.x.rem_euclid(width);
-        ret.y = ret.y.rem_euclid(height);
-        ret
+    fn simulate(&self, width: i64, height: i64, seconds: i64) -> Point2<i64> {
+        (self.position + self.velocity * seconds).rem_euclid(&Vector2::new(width, height))
+    }
+
+    /// Parses a `<prefix><x>,<y>` pair, where `part` is a subslice of `input`.
+    fn parse_pair(input: &str, part: &str, prefix: &str) -> Result<(i64, i64), ParseError> {
+        let (x, y) = part
+            .strip_prefix(prefix)
+            .and_then(|rest| rest.split_once(','))
+            .parse_err_at(input, part, &format!("expected \"{}<x>,<y>\"", prefix))?;
+
+        Ok((
+            x.parse().parse_err_at(input, x, "invalid x value")?,
+            y.parse().parse_err_at(input, y, "invalid y value")?,
+        ))
     }
 }
 
 impl FromStr for Robot {
-    type Err = anyhow::Error;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let (left, right) = s
             .split_once(' ')
-            .ok_or_else(|| anyhow!("invalid robot input"))?;
-
-        let position = left[2..left.len()]
-            .split_once(',')
-            .and_then(|(l, r)| {
-                l.parse::<i64>()
-                    .and_then(|x| r.parse::<i64>().map(|y| Point3::new(x, y, 0)))
-                    .ok()
-            })
-            .ok_or_else(|| anyhow!("invalid position input"))?;
-
-        let velocity = right[2..right.len()]
-            .split_once(',')
-            .and_then(|(l, r)| {
-                l.parse::<i64>()
-                    .and_then(|x| r.parse::<i64>().map(|y| Vector3::new(x, y, 0)))
-                    .ok()
-            })
-            .ok_or_else(|| anyhow!("invalid velocity input"))?;
-
-        Ok(Self { position, velocity })
+            .parse_err_at(s, s, "invalid robot input")?;
+
+        let (px, py) = Self::parse_pair(s, left, "p=")?;
+        let (vx, vy) = Self::parse_pair(s, right, "v=")?;
+
+        Ok(Self {
+            position: Point2::new(px, py),
+            velocity: Vector2::new(vx, vy),
+        })
     }
 }
 
@@ -117,13 +115,13 @@ impl RestroomRedoubt {
 }
 
 impl FromStr for RestroomRedoubt {
-    type Err = anyhow::Error;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         Ok(Self {
             robots: s
                 .lines()
-                .map(Robot::from_str)
+                .map(|l| Robot::from_str(l).map_err(|e| e.within(s, l)))
                 .collect::<Result<Vec<_>, _>>()?,
         })
     }
@@ -147,12 +145,16 @@ impl Problem for RestroomRedoubt {
     }
 }
 
+aoc_plumbing::register_problem!(RestroomRedoubt);
+
 #[cfg(test)]
 mod tests {
     use aoc_plumbing::Solution;
 
     use super::*;
 
+    aoc_plumbing::answers_test!(RestroomRedoubt);
+
     #[test]
     #[ignore]
     fn full_dataset() {
@@ -1,13 +1,15 @@
 use std::str::FromStr;
 
-use anyhow::{anyhow, bail};
 use aoc_common::{
     direction::Cardinal,
     grid::{Coordinate, Grid},
 };
-use aoc_plumbing::Problem;
+use aoc_plumbing::{ParseError, ParseErrorContext, Problem};
 use rustc_hash::FxHashMap;
 
+#[cfg(feature = "record")]
+use aoc_common::record::Recording;
+
 #[derive(Debug, Clone)]
 pub struct WarehouseWoes {
     grid: Grid<char>,
@@ -15,54 +17,54 @@ pub struct WarehouseWoes {
     robot: Coordinate,
     wide_robot: Coordinate,
     moves: Vec<Cardinal>,
+    #[cfg(feature = "record")]
+    recording: Recording<char>,
 }
 
 impl FromStr for WarehouseWoes {
-    type Err = anyhow::Error;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (top, bottom) = s
-            .split_once("\n\n")
-            .ok_or_else(|| anyhow!("invalid input"))?;
-
-        let grid = Grid::from_str(top)?;
-        let robot = grid
-            .find_coordinate(|&x| x == '@')
-            .ok_or_else(|| anyhow!("could not find robot in grid"))?;
+        let (top, bottom) = s.split_once("\n\n").parse_err_at(
+            s,
+            &s[s.len()..],
+            "expected a blank line before the moves",
+        )?;
+
+        let grid =
+            Grid::from_str(top).map_err(|e| ParseError::at_line(s, e.row(), e.to_string()))?;
+        let robot = grid.find_coordinate(|&x| x == '@').parse_err_at(
+            s,
+            top,
+            "could not find robot in grid",
+        )?;
         let moves = bottom
-            .chars()
-            .filter(|x| *x != '\n')
-            .map(|x| match x {
+            .char_indices()
+            .filter(|(_, x)| *x != '\n')
+            .map(|(offset, x)| match x {
                 '^' => Ok(Cardinal::North),
                 '>' => Ok(Cardinal::East),
                 '<' => Ok(Cardinal::West),
                 'v' => Ok(Cardinal::South),
-                _ => bail!("invalid moves"),
+                _ => Err(ParseError::at(
+                    s,
+                    &bottom[offset..offset + x.len_utf8()],
+                    "invalid move",
+                )),
             })
             .collect::<Result<Vec<_>, _>>()?;
 
-        let mut wide_grid = Grid::new(grid.n, grid.m * 2, '.');
-        for i in 0..grid.n {
-            for j in 0..grid.m {
-                match grid[(i, j).into()] {
-                    '#' => {
-                        wide_grid[(i, j * 2).into()] = '#';
-                        wide_grid[(i, j * 2 + 1).into()] = '#';
-                    }
-                    'O' => {
-                        wide_grid[(i, j * 2).into()] = '[';
-                        wide_grid[(i, j * 2 + 1).into()] = ']';
-                    }
-                    '@' => {
-                        wide_grid[(i, j * 2).into()] = '@';
-                    }
-                    _ => (),
-                }
-            }
-        }
-        let wide_robot = wide_grid
-            .find_coordinate(|&x| x == '@')
-            .ok_or_else(|| anyhow!("could not find robot in wide grid"))?;
+        let wide_grid = grid.map_cells(|c| match c {
+            '#' => ['#', '#'],
+            'O' => ['[', ']'],
+            '@' => ['@', '.'],
+            _ => ['.', '.'],
+        });
+        let wide_robot = wide_grid.find_coordinate(|&x| x == '@').parse_err_at(
+            s,
+            top,
+            "could not find robot in wide grid",
+        )?;
 
         Ok(Self {
             grid,
@@ -70,12 +72,24 @@ impl FromStr for WarehouseWoes {
             robot,
             wide_robot,
             wide_grid,
+            #[cfg(feature = "record")]
+            recording: Recording::default(),
         })
     }
 }
 
 impl WarehouseWoes {
+    /// Records a frame of the (narrow) warehouse before the first move and
+    /// after every move made in part one.
+    #[cfg(feature = "record")]
+    pub fn record(&mut self, recording: Recording<char>) {
+        self.recording = recording;
+    }
+
     fn simulate(&mut self) {
+        #[cfg(feature = "record")]
+        self.recording.frame(&self.grid);
+
         for dir in &self.moves.clone() {
             self.simulate_one(dir);
         }
@@ -107,6 +121,9 @@ impl WarehouseWoes {
 
             coord = coord.neighbour(dir);
         }
+
+        #[cfg(feature = "record")]
+        self.recording.frame(&self.grid);
     }
 
     fn sum_gps(&self) -> usize {
@@ -249,12 +266,16 @@ impl Problem for WarehouseWoes {
     }
 }
 
+aoc_plumbing::register_problem!(WarehouseWoes);
+
 #[cfg(test)]
 mod tests {
     use aoc_plumbing::Solution;
 
     use super::*;
 
+    aoc_plumbing::answers_test!(WarehouseWoes);
+
     #[test]
     #[ignore]
     fn full_dataset() {
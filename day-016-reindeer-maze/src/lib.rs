@@ -4,9 +4,9 @@ use anyhow::anyhow;
 use aoc_common::{
     direction::Cardinal,
     grid::{Coordinate, Grid},
-    pathfinding,
+    pathfinding::{self, ShortestPaths},
 };
-use aoc_plumbing::Problem;
+use aoc_plumbing::{ParseError, ParseErrorContext, Problem};
 use rustc_hash::FxHashSet;
 
 #[derive(Debug, Clone)]
@@ -14,26 +14,26 @@ pub struct ReindeerMaze {
     grid: Grid<char>,
     start: Coordinate,
     end: Coordinate,
-    dijkstra_result: Option<(i64, Vec<Vec<Node>>)>,
+    paths: Option<ShortestPaths<Node>>,
 }
 
 impl FromStr for ReindeerMaze {
-    type Err = anyhow::Error;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let grid = Grid::from_str(s)?;
-        let start = grid
-            .find_coordinate(|&x| x == 'S')
-            .ok_or_else(|| anyhow!("could not find start"))?;
+        let grid = Grid::from_str(s).map_err(|e| ParseError::at_line(s, e.row(), e.to_string()))?;
+        let start =
+            grid.find_coordinate(|&x| x == 'S')
+                .parse_err_at(s, s, "could not find start")?;
         let end = grid
             .find_coordinate(|&x| x == 'E')
-            .ok_or_else(|| anyhow!("could not find end"))?;
+            .parse_err_at(s, s, "could not find end")?;
 
         Ok(Self {
             grid,
             start,
             end,
-            dijkstra_result: None,
+            paths: None,
         })
     }
 }
@@ -77,30 +77,32 @@ impl ReindeerMaze {
                 .filter(|(n, _)| self.grid.get(n.coord).is_some_and(|c| c == '.' || c == 'E'))
         };
 
-        let success = |x: &Node| x.coord == self.end;
-
         let start = Node {
             coord: self.start,
             facing: Cardinal::East,
         };
 
-        self.dijkstra_result = pathfinding::dijkstra_with_paths(&start, successors, success);
-        self.dijkstra_result.as_ref().map(|(cost, _)| *cost)
+        let success = |x: &Node| x.coord == self.end;
+
+        self.paths = pathfinding::dijkstra_dag(&start, successors, success);
+        self.paths.as_ref().map(|p| p.cost())
     }
 
-    fn part2(&self) -> Option<usize> {
-        match &self.dijkstra_result {
-            Some((_, paths)) => {
-                let coords = paths
-                    .iter()
-                    .flatten()
-                    .map(|x| x.coord)
-                    .collect::<FxHashSet<_>>();
-
-                Some(coords.len())
-            }
-            None => None,
+    fn part2(&mut self) -> Option<usize> {
+        // the paths are found by part 1, which may not have been run
+        if self.paths.is_none() {
+            self.part1()?;
         }
+
+        let coords = self
+            .paths
+            .as_ref()?
+            .nodes()
+            .into_iter()
+            .map(|x| x.coord)
+            .collect::<FxHashSet<_>>();
+
+        Some(coords.len())
     }
 }
 
@@ -128,12 +130,16 @@ impl Problem for ReindeerMaze {
     }
 }
 
+aoc_plumbing::register_problem!(ReindeerMaze);
+
 #[cfg(test)]
 mod tests {
     use aoc_plumbing::Solution;
 
     use super::*;
 
+    aoc_plumbing::answers_test!(ReindeerMaze);
+
     #[test]
     #[ignore]
     fn full_dataset() {
@@ -147,5 +153,6 @@ mod tests {
         let input = std::fs::read_to_string("example.txt").expect("Unable to load input");
         let solution = ReindeerMaze::solve(&input).unwrap();
         assert_eq!(solution, Solution::new(7036, 45));
+        assert_eq!(ReindeerMaze::solve_part_two(&input).unwrap().answer, 45);
     }
 }
@@ -0,0 +1,320 @@
+//! An interactive, single-stepping debugger for the computer's programs.
+//!
+//! The debugger redraws a screen showing the program, the registers, the
+//! instruction pointer and the output after every command, and reads commands
+//! one line at a time:
+//!
+//! ```text
+//! s [n]          step one (or n) instructions; an empty line also steps
+//! c              continue until a breakpoint, output, or the program halts
+//! b <ip> | out   break before the instruction at <ip>, or on every output
+//! d [<ip> | out] delete a breakpoint, or all of them
+//! set <reg> <v>  set register a, b or c, in decimal or 0o-prefixed octal
+//! r [a]          restart, optionally with a new value for register A
+//! q              quit
+//! ```
+use std::{
+    collections::BTreeSet,
+    io::{self, BufRead, Write},
+};
+
+use itertools::join;
+
+use crate::{vm::disassemble_line, ChronospatialComputer, Step, Vm};
+
+/// `continue` gives up after this many instructions, in case the program
+/// never halts.
+const CONTINUE_LIMIT: usize = 10_000_000;
+
+/// Why the debugger stopped running the program.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Stop {
+    /// The requested number of instructions were executed.
+    Stepped,
+    /// The instruction pointer reached a breakpoint.
+    Breakpoint(usize),
+    /// The program output a value while breaking on output was enabled.
+    Output(u8),
+    /// The program halted.
+    Halted,
+    /// `continue` executed too many instructions without stopping.
+    Limit,
+}
+
+/// Steps through a program one instruction at a time, stopping at
+/// breakpoints.
+#[derive(Debug, Clone)]
+pub struct Debugger {
+    initial: Vm,
+    vm: Vm,
+    output: Vec<u8>,
+    breakpoints: BTreeSet<usize>,
+    break_on_output: bool,
+}
+
+impl Debugger {
+    pub fn new(computer: &ChronospatialComputer) -> Self {
+        Self {
+            initial: computer.vm(),
+            vm: computer.vm(),
+            output: Vec::default(),
+            breakpoints: BTreeSet::default(),
+            break_on_output: false,
+        }
+    }
+
+    /// The registers A, B and C.
+    pub fn registers(&self) -> (u64, u64, u64) {
+        self.vm.registers()
+    }
+
+    /// The index of the next instruction to execute.
+    pub fn ip(&self) -> usize {
+        self.vm.ip()
+    }
+
+    /// Everything output since the program was (re)started.
+    pub fn output(&self) -> &[u8] {
+        &self.output
+    }
+
+    /// Whether the instruction pointer is past the end of the program.
+    pub fn is_halted(&self) -> bool {
+        self.vm.is_halted()
+    }
+
+    pub fn add_breakpoint(&mut self, ip: usize) {
+        self.breakpoints.insert(ip);
+    }
+
+    pub fn remove_breakpoint(&mut self, ip: usize) -> bool {
+        self.breakpoints.remove(&ip)
+    }
+
+    pub fn break_on_output(&mut self, enabled: bool) {
+        self.break_on_output = enabled;
+    }
+
+    /// Resets the registers, instruction pointer and output to how they were
+    /// at the start, optionally with a different value in register A.
+    /// Breakpoints are kept.
+    pub fn restart(&mut self, a: Option<u64>) {
+        if let Some(a) = a {
+            let (_, b, c) = self.initial.registers();
+            self.initial.set_registers(a, b, c);
+        }
+        self.vm = self.initial.clone();
+        self.output.clear();
+    }
+
+    /// Executes one instruction.
+    pub fn step(&mut self) -> Step {
+        // the debugger's VM has no instruction limit, so stepping can't fail
+        let step = self.vm.step().unwrap_or(Step::Halt);
+        if let Step::Output(x) = step {
+            self.output.push(x);
+        }
+
+        step
+    }
+
+    /// Executes up to `n` instructions, stopping early if the program halts,
+    /// outputs while breaking on output, or reaches a breakpoint after the
+    /// first instruction.
+    pub fn step_n(&mut self, n: usize) -> Stop {
+        for i in 0..n {
+            if i > 0 && self.breakpoints.contains(&self.vm.ip()) {
+                return Stop::Breakpoint(self.vm.ip());
+            }
+
+            match self.step() {
+                Step::Continue => (),
+                Step::Output(x) if self.break_on_output => return Stop::Output(x),
+                Step::Output(_) => (),
+                Step::Halt => return Stop::Halted,
+            }
+        }
+
+        if self.is_halted() {
+            Stop::Halted
+        } else {
+            Stop::Stepped
+        }
+    }
+
+    /// Runs until a breakpoint, an output when breaking on output, or the
+    /// program halts.
+    pub fn cont(&mut self) -> Stop {
+        match self.step_n(CONTINUE_LIMIT) {
+            Stop::Stepped => Stop::Limit,
+            stop => stop,
+        }
+    }
+
+    /// Runs the interactive debugger, reading commands from `input` and
+    /// drawing to `out` until `q` or the end of the input.
+    ///
+    /// If `clear` is set, the screen is cleared before each redraw, which
+    /// should only be done when `out` is a terminal.
+    pub fn run(&mut self, input: impl BufRead, mut out: impl Write, clear: bool) -> io::Result<()> {
+        let mut lines = input.lines();
+        let mut status = String::from("ready");
+
+        loop {
+            if clear {
+                write!(out, "\x1b[2J\x1b[H")?;
+            }
+            self.draw(&mut out, &status)?;
+            write!(out, "> ")?;
+            out.flush()?;
+
+            let Some(line) = lines.next().transpose()? else {
+                writeln!(out)?;
+                return Ok(());
+            };
+
+            match self.command(line.trim()) {
+                Some(s) => status = s,
+                None => return Ok(()),
+            }
+        }
+    }
+
+    /// Executes a single command, returning the status to show, or `None`
+    /// to quit.
+    fn command(&mut self, line: &str) -> Option<String> {
+        let words = line.split_whitespace().collect::<Vec<_>>();
+
+        let status = match words[..] {
+            [] | ["s"] => {
+                let stop = self.step_n(1);
+                self.describe(stop)
+            }
+            ["s", n] => match parse_number(n) {
+                Some(n) => {
+                    let stop = self.step_n(n as usize);
+                    self.describe(stop)
+                }
+                None => format!("invalid step count {:?}", n),
+            },
+            ["c"] => {
+                let stop = self.cont();
+                self.describe(stop)
+            }
+            ["b", "out"] => {
+                self.break_on_output(true);
+                "breaking on output".to_string()
+            }
+            ["b", ip] => match parse_number(ip) {
+                Some(ip) if (ip as usize) < self.vm.program().ops().len() => {
+                    self.add_breakpoint(ip as usize);
+                    format!("breakpoint at {}", ip)
+                }
+                _ => format!("no instruction at {:?}", ip),
+            },
+            ["d"] => {
+                self.breakpoints.clear();
+                self.break_on_output(false);
+                "deleted all breakpoints".to_string()
+            }
+            ["d", "out"] => {
+                self.break_on_output(false);
+                "no longer breaking on output".to_string()
+            }
+            ["d", ip] => match parse_number(ip) {
+                Some(ip) if self.remove_breakpoint(ip as usize) => {
+                    format!("deleted breakpoint at {}", ip)
+                }
+                _ => format!("no breakpoint at {:?}", ip),
+            },
+            ["set", reg, v] => {
+                let (a, b, c) = self.vm.registers();
+                match (reg.to_ascii_lowercase().as_str(), parse_number(v)) {
+                    ("a", Some(v)) => {
+                        self.vm.set_registers(v, b, c);
+                        format!("A = {}", v)
+                    }
+                    ("b", Some(v)) => {
+                        self.vm.set_registers(a, v, c);
+                        format!("B = {}", v)
+                    }
+                    ("c", Some(v)) => {
+                        self.vm.set_registers(a, b, v);
+                        format!("C = {}", v)
+                    }
+                    _ => format!("cannot set {:?} to {:?}", reg, v),
+                }
+            }
+            ["r"] => {
+                self.restart(None);
+                "restarted".to_string()
+            }
+            ["r", a] => match parse_number(a) {
+                Some(a) => {
+                    self.restart(Some(a));
+                    format!("restarted with A = {}", a)
+                }
+                None => format!("invalid value {:?}", a),
+            },
+            ["q"] => return None,
+            _ => format!("unknown command {:?}", line),
+        };
+
+        Some(status)
+    }
+
+    fn describe(&self, stop: Stop) -> String {
+        match stop {
+            Stop::Stepped => format!("stepped to {}", self.vm.ip()),
+            Stop::Breakpoint(ip) => format!("hit breakpoint at {}", ip),
+            Stop::Output(x) => format!("output {}", x),
+            Stop::Halted => "halted".to_string(),
+            Stop::Limit => format!("stopped after {} instructions", CONTINUE_LIMIT),
+        }
+    }
+
+    fn draw(&self, out: &mut impl Write, status: &str) -> io::Result<()> {
+        writeln!(out, "chronospatial computer, step {}", self.vm.executed())?;
+        writeln!(out)?;
+
+        for (ip, op) in self.vm.program().ops().iter().enumerate() {
+            let cursor = if ip == self.vm.ip() { ">" } else { " " };
+            let breakpoint = if self.breakpoints.contains(&ip) {
+                "*"
+            } else {
+                " "
+            };
+            writeln!(out, "{}{} {}", cursor, breakpoint, disassemble_line(ip, op))?;
+        }
+        if self.is_halted() {
+            writeln!(out, ">  end")?;
+        }
+
+        writeln!(out)?;
+        let (a, b, c) = self.vm.registers();
+        for (name, value) in [("A", a), ("B", b), ("C", c)] {
+            writeln!(out, "{} = {:<20} 0o{:o}", name, value, value)?;
+        }
+        writeln!(out)?;
+        writeln!(out, "output: {}", join(&self.output, ","))?;
+        writeln!(
+            out,
+            "break on output: {}",
+            if self.break_on_output { "on" } else { "off" }
+        )?;
+        writeln!(out)?;
+        writeln!(out, "{}", status)?;
+        writeln!(
+            out,
+            "[s]tep [n], [c]ontinue, [b]reak <ip>|out, [d]elete [<ip>|out], set <reg> <v>, [r]estart [a], [q]uit"
+        )
+    }
+}
+
+/// Parses a decimal or `0o`-prefixed octal number.
+fn parse_number(s: &str) -> Option<u64> {
+    match s.strip_prefix("0o") {
+        Some(octal) => u64::from_str_radix(octal, 8).ok(),
+        None => s.parse().ok(),
+    }
+}
@@ -1,216 +1,123 @@
 use std::str::FromStr;
 
-use anyhow::{anyhow, bail};
-use aoc_plumbing::Problem;
+use anyhow::{anyhow, Context};
+use aoc_plumbing::{ParseError, ParseErrorContext, Problem};
 use itertools::join;
 
-#[derive(Debug, Clone, Copy, Eq, PartialEq)]
-enum Op {
-    Adv(Operand),
-    Bxl(Operand),
-    Bst(Operand),
-    Jnz(Operand),
-    Bxc(Operand),
-    Out(Operand),
-    Bdv(Operand),
-    Cdv(Operand),
-}
-
-impl TryFrom<(u8, u8)> for Op {
-    type Error = anyhow::Error;
-
-    fn try_from(value: (u8, u8)) -> Result<Self, Self::Error> {
-        let ret = match value.0 {
-            0 => Self::Adv(value.1.try_into()?),
-            1 => Self::Bxl(Operand::Literal(value.1)),
-            2 => Self::Bst(value.1.try_into()?),
-            3 => Self::Jnz(Operand::Literal(value.1)),
-            4 => Self::Bxc(Operand::Literal(value.1)),
-            5 => Self::Out(value.1.try_into()?),
-            6 => Self::Bdv(value.1.try_into()?),
-            7 => Self::Cdv(value.1.try_into()?),
-            _ => bail!("invalid op"),
-        };
+pub mod debugger;
+pub mod symbolic;
+pub mod vm;
 
-        Ok(ret)
-    }
-}
+pub use vm::{Op, Operand, Program, Step, Vm, VmError};
 
-#[derive(Debug, Clone, Copy, Eq, PartialEq)]
-enum Operand {
-    Literal(u8),
-    RegisterA,
-    RegisterB,
-    RegisterC,
-}
-
-impl TryFrom<u8> for Operand {
-    type Error = anyhow::Error;
-
-    fn try_from(value: u8) -> Result<Self, Self::Error> {
-        let ret = match value {
-            0..=3 => Self::Literal(value),
-            4 => Self::RegisterA,
-            5 => Self::RegisterB,
-            6 => Self::RegisterC,
-            _ => bail!("invalid operand"),
-        };
-
-        Ok(ret)
-    }
-}
+/// Part one gives up after this many instructions, in case the program never
+/// halts.
+const INSTRUCTION_LIMIT: usize = 10_000_000;
 
 #[derive(Debug, Clone)]
 pub struct ChronospatialComputer {
-    register_a: u64,
-    register_b: u64,
-    register_c: u64,
-    ops: Vec<Op>,
-    raw: Vec<u8>,
+    registers: (u64, u64, u64),
+    program: Program,
 }
 
 impl FromStr for ChronospatialComputer {
-    type Err = anyhow::Error;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let mut lines = s.lines();
-        let register_a = lines
-            .next()
-            .and_then(|l| l[12..l.len()].parse().ok())
-            .ok_or_else(|| anyhow!("could not parse register a"))?;
-        let register_b = lines
-            .next()
-            .and_then(|l| l[12..l.len()].parse().ok())
-            .ok_or_else(|| anyhow!("could not parse register b"))?;
-        let register_c = lines
-            .next()
-            .and_then(|l| l[12..l.len()].parse().ok())
-            .ok_or_else(|| anyhow!("could not parse register c"))?;
-
-        lines.next();
-
-        let raw = lines
-            .next()
-            .and_then(|l| {
-                l[9..l.len()]
-                    .split(',')
-                    .map(|x| x.parse::<u8>())
-                    .collect::<Result<Vec<_>, _>>()
-                    .ok()
-            })
-            .ok_or_else(|| anyhow!("could not parse codes"))?;
+        let mut next_line = || {
+            lines
+                .next()
+                .ok_or_else(|| ParseError::at_end(s, "unexpected end of input"))
+        };
+
+        let register_a = Self::parse_register(s, next_line()?, "Register A: ")?;
+        let register_b = Self::parse_register(s, next_line()?, "Register B: ")?;
+        let register_c = Self::parse_register(s, next_line()?, "Register C: ")?;
+
+        next_line()?;
+
+        let program = next_line()?;
+        let codes = program
+            .strip_prefix("Program: ")
+            .parse_err_at(s, program, "expected \"Program: \"")?
+            .split(',')
+            .collect::<Vec<_>>();
 
-        let ops = raw
+        let raw = codes
+            .iter()
+            .map(|x| x.parse::<u8>().parse_err_at(s, x, "invalid code"))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let ops = codes
             .chunks(2)
-            .map(|x| Op::try_from((x[0], x[1])))
+            .zip(raw.chunks(2))
+            .map(|(c, x)| match x {
+                [op, operand] => {
+                    Op::try_from((*op, *operand)).parse_err_at(s, c[0], "invalid instruction")
+                }
+                _ => Err(ParseError::at(
+                    s,
+                    c[0],
+                    "instruction is missing its operand",
+                )),
+            })
             .collect::<Result<Vec<_>, _>>()?;
 
         Ok(Self {
-            register_a,
-            register_b,
-            register_c,
-            ops,
-            raw,
+            registers: (register_a, register_b, register_c),
+            program: Program::new(ops),
         })
     }
 }
 
 impl ChronospatialComputer {
-    fn simulate(&mut self) -> String {
-        let mut head = 0;
-        let mut ret = Vec::default();
-
-        while head < self.ops.len() {
-            let op = &self.ops[head];
-            match op {
-                Op::Adv(x) => self.register_a /= 2_u64.pow(self.value(x) as u32),
-                Op::Bxl(x) => self.register_b ^= self.value(x),
-                Op::Bst(x) => self.register_b = self.value(x) % 8,
-                Op::Jnz(x) => match self.register_a {
-                    0 => (),
-                    _ => {
-                        head = self.value(x) as usize / 2;
-                        continue;
-                    }
-                },
-                Op::Bxc(_) => self.register_b ^= self.register_c,
-                Op::Out(x) => ret.push(self.value(x) % 8),
-                Op::Bdv(x) => self.register_b = self.register_a / 2_u64.pow(self.value(x) as u32),
-                Op::Cdv(x) => self.register_c = self.register_a / 2_u64.pow(self.value(x) as u32),
-            }
-
-            head += 1;
-        }
-
-        join(ret, ",")
+    /// Parses a `<prefix><value>` register line, where `line` is a subslice
+    /// of `input`.
+    fn parse_register(input: &str, line: &str, prefix: &str) -> Result<u64, ParseError> {
+        let value = line.strip_prefix(prefix).parse_err_at(
+            input,
+            line,
+            &format!("expected {:?}", prefix),
+        )?;
+
+        value
+            .parse()
+            .parse_err_at(input, value, "invalid register value")
     }
 
-    fn simulate_one_cycle(&mut self) -> Option<u8> {
-        let mut head = 0;
-        let mut ret = None;
-
-        while head < self.ops.len() {
-            let op = &self.ops[head];
-            match op {
-                Op::Adv(x) => self.register_a /= 2_u64.pow(self.value(x) as u32),
-                Op::Bxl(x) => self.register_b ^= self.value(x),
-                Op::Bst(x) => self.register_b = self.value(x) % 8,
-                Op::Jnz(_) => match self.register_a {
-                    0 => (),
-                    _ => break,
-                },
-                Op::Bxc(_) => self.register_b ^= self.register_c,
-                Op::Out(x) => ret = Some((self.value(x) % 8) as u8),
-                Op::Bdv(x) => self.register_b = self.register_a / 2_u64.pow(self.value(x) as u32),
-                Op::Cdv(x) => self.register_c = self.register_a / 2_u64.pow(self.value(x) as u32),
-            }
-
-            head += 1;
-        }
-
-        ret
+    pub fn program(&self) -> &Program {
+        &self.program
     }
 
-    fn find_register(&mut self) -> Option<u64> {
-        self.find_register_helper(0, &self.raw.clone())
+    /// A VM loaded with the program and the input's registers.
+    pub fn vm(&self) -> Vm {
+        let (a, b, c) = self.registers;
+        Vm::new(self.program.clone()).with_registers(a, b, c)
     }
 
-    fn find_register_helper(&mut self, acc: u64, desired: &[u8]) -> Option<u64> {
-        if desired.is_empty() {
-            return Some(acc);
-        }
-
-        let code = desired[desired.len() - 1];
-
-        let range = if acc == 0 {
-            0o1..0o10
-        } else {
-            (acc * 0o10)..(acc * 0o10 + 0o10)
-        };
-
-        for i in range {
-            self.register_a = i;
-            self.register_b = 0;
-            self.register_c = 0;
+    fn simulate(&self) -> anyhow::Result<String> {
+        let output = self.vm().limit(INSTRUCTION_LIMIT).run()?;
+        Ok(join(output, ","))
+    }
 
-            if self.simulate_one_cycle().is_some_and(|x| x == code) {
-                let result = self.find_register_helper(i, &desired[0..desired.len() - 1]);
-                if result.is_some() {
-                    return result;
-                }
-            }
-        }
+    /// The program as assembly. See [`Program::disassemble`].
+    pub fn disassemble(&self) -> String {
+        self.program.disassemble()
+    }
 
-        None
+    /// Symbolically evaluates the program's loop. See [`symbolic`].
+    pub fn analyse(&self) -> anyhow::Result<symbolic::Analysis> {
+        symbolic::analyse(self.program.ops())
     }
 
-    fn value(&self, operand: &Operand) -> u64 {
-        match operand {
-            Operand::Literal(x) => *x as u64,
-            Operand::RegisterA => self.register_a,
-            Operand::RegisterB => self.register_b,
-            Operand::RegisterC => self.register_c,
-        }
+    /// Finds the smallest value of register A for which the program outputs
+    /// its own codes.
+    fn find_register(&self) -> anyhow::Result<u64> {
+        self.analyse()
+            .context("cannot solve this program")?
+            .solve(&self.program.codes())
+            .ok_or_else(|| anyhow!("no value of A makes the program output itself"))
     }
 }
 
@@ -224,20 +131,24 @@ impl Problem for ChronospatialComputer {
     type P2 = u64;
 
     fn part_one(&mut self) -> Result<Self::P1, Self::ProblemError> {
-        Ok(self.simulate())
+        self.simulate()
     }
 
     fn part_two(&mut self) -> Result<Self::P2, Self::ProblemError> {
         self.find_register()
-            .ok_or_else(|| anyhow!("could not find value"))
     }
 }
 
+aoc_plumbing::register_problem!(ChronospatialComputer);
+
 #[cfg(test)]
 mod tests {
     use aoc_plumbing::Solution;
 
     use super::*;
+    use crate::debugger::{Debugger, Stop};
+
+    aoc_plumbing::answers_test!(ChronospatialComputer);
 
     #[test]
     #[ignore]
@@ -266,4 +177,187 @@ mod tests {
         let solution = ChronospatialComputer::solve(&input).unwrap();
         assert_eq!(solution, Solution::new("5,7,3,0".to_owned(), 117440));
     }
+
+    #[test]
+    fn disassemble() {
+        let input = std::fs::read_to_string("example2.txt").expect("Unable to load input");
+        let problem = ChronospatialComputer::from_str(&input).expect("could not parse input");
+        assert_eq!(
+            problem.disassemble(),
+            "  0  adv 3   ; A = A >> 3\n  1  out A   ; out A & 7\n  2  jnz 0   ; if A != 0 goto 0\n"
+        );
+    }
+
+    #[test]
+    fn symbolic() {
+        let input = "Register A: 0\nRegister B: 0\nRegister C: 0\n\nProgram: 2,4,1,3,7,5,4,1,1,3,0,3,5,5,3,0\n";
+        let mut problem = ChronospatialComputer::from_str(input).expect("could not parse input");
+        let analysis = problem.analyse().unwrap();
+
+        assert_eq!(analysis.shift, 3);
+        assert_eq!(analysis.window(), Some(0..10));
+        assert_eq!(
+            analysis.to_string(),
+            "each iteration shifts A right by 3 bits\n\
+             the outputs depend on bits 0..10 of A at the start of the iteration\n\
+             out[0] = (((((A & 7) ^ 3) ^ (A >> ((A & 7) ^ 3))) ^ 3) & 7)"
+        );
+
+        let a = problem.part_two().unwrap();
+        problem.registers = (a, 0, 0);
+        assert_eq!(
+            problem.part_one().unwrap(),
+            join(problem.program.codes(), ",")
+        );
+    }
+
+    #[test]
+    fn unsolvable() {
+        let parse = |program: &str| {
+            let input =
+                format!("Register A: 0\nRegister B: 0\nRegister C: 0\n\nProgram: {program}\n");
+            ChronospatialComputer::from_str(&input).expect("could not parse input")
+        };
+
+        let err = parse("1,1,5,5,0,3,3,0").part_two().unwrap_err();
+        assert_eq!(
+            format!("{:#}", err),
+            "cannot solve this program: an output depends on the previous iteration: ((B' ^ 1) & 7)"
+        );
+
+        let err = parse("0,4,5,4,3,0").part_two().unwrap_err();
+        assert_eq!(
+            format!("{:#}", err),
+            "cannot solve this program: A is not shifted by a constant each iteration: (A >> A)"
+        );
+
+        let err = parse("0,3,5,4").part_two().unwrap_err();
+        assert_eq!(
+            format!("{:#}", err),
+            "cannot solve this program: the program does not end with a jump back to the start"
+        );
+    }
+
+    #[test]
+    fn assembler() {
+        let input = "Register A: 0\nRegister B: 0\nRegister C: 0\n\nProgram: 2,4,1,3,7,5,4,1,1,3,0,3,5,5,3,0\n";
+        let problem = ChronospatialComputer::from_str(input).expect("could not parse input");
+
+        let program = problem.program();
+        assert_eq!(problem.disassemble().parse::<Program>().unwrap(), *program);
+        assert_eq!(program.to_string().parse::<Program>().unwrap(), *program);
+        assert_eq!(Program::from_codes(&program.codes()).unwrap(), *program);
+
+        let program: Program = "BST a\n\n  # comment\nbxc 7\njnz 6 ; loop".parse().unwrap();
+        assert_eq!(program.codes(), vec![2, 4, 4, 7, 3, 6]);
+
+        let input = "adv 1\nbxl A\n";
+        let err = input.parse::<Program>().unwrap_err();
+        assert_eq!((err.line, err.column), (2, 5));
+        assert_eq!(err.message, "invalid operand");
+
+        let err = "out 5".parse::<Program>().unwrap_err();
+        assert_eq!(
+            err.message,
+            "combo literals must be 0 to 3; use A, B or C for registers"
+        );
+        assert_eq!(
+            "jmp 0".parse::<Program>().unwrap_err().message,
+            "unknown instruction"
+        );
+        assert_eq!(
+            "adv".parse::<Program>().unwrap_err().message,
+            "missing operand"
+        );
+        assert_eq!("adv 1 2".parse::<Program>().unwrap_err().snippet, "2");
+    }
+
+    #[test]
+    fn vm() {
+        // shifting by more than 63 bits clears the register instead of
+        // overflowing
+        let program: Program = "bdv A\ncdv A\nadv A\nout B\nout C".parse().unwrap();
+        let mut vm = Vm::new(program).with_registers(100, 0, 0);
+        assert_eq!(vm.run(), Ok(vec![0, 0]));
+        assert_eq!(vm.registers(), (0, 0, 0));
+        assert!(vm.is_halted());
+        assert_eq!(vm.executed(), 5);
+
+        let program: Program = "bxl 7\nout B".parse().unwrap();
+        let mut vm = Vm::new(program).width(2);
+        assert_eq!(vm.run(), Ok(vec![3]));
+
+        let program: Program = "out A\njnz 0".parse().unwrap();
+        let mut vm = Vm::new(program).with_registers(1, 0, 0).limit(5);
+        let mut outputs = vm.outputs();
+        assert_eq!(outputs.next(), Some(Ok(1)));
+        assert_eq!(outputs.next(), Some(Ok(1)));
+        assert_eq!(outputs.next(), Some(Ok(1)));
+        assert_eq!(outputs.next(), Some(Err(VmError::Limit(5))));
+        assert_eq!(outputs.next(), None);
+
+        let input = "Register A: 1\nRegister B: 0\nRegister C: 0\n\nProgram: 3,0\n";
+        let mut problem = ChronospatialComputer::from_str(input).expect("could not parse input");
+        assert_eq!(
+            problem.part_one().unwrap_err().to_string(),
+            "the program did not halt within 10000000 instructions"
+        );
+    }
+
+    #[test]
+    fn debugger() {
+        let input = std::fs::read_to_string("example.txt").expect("Unable to load input");
+        let problem = ChronospatialComputer::from_str(&input).expect("could not parse input");
+        let mut debugger = Debugger::new(&problem);
+
+        debugger.break_on_output(true);
+        assert_eq!(debugger.cont(), Stop::Output(4));
+        assert_eq!(debugger.registers(), (364, 0, 0));
+
+        debugger.break_on_output(false);
+        debugger.add_breakpoint(1);
+        assert_eq!(debugger.cont(), Stop::Breakpoint(1));
+        assert_eq!(debugger.output(), &[4]);
+        assert_eq!(debugger.registers(), (182, 0, 0));
+
+        debugger.remove_breakpoint(1);
+        assert_eq!(debugger.cont(), Stop::Halted);
+        assert_eq!(debugger.output(), &[4, 6, 3, 5, 6, 3, 5, 2, 1, 0]);
+
+        debugger.restart(Some(0o10));
+        assert_eq!(debugger.step_n(100), Stop::Halted);
+        assert_eq!(debugger.output(), &[4, 2, 1, 0]);
+    }
+
+    #[test]
+    fn debugger_commands() {
+        let input = std::fs::read_to_string("example.txt").expect("Unable to load input");
+        let problem = ChronospatialComputer::from_str(&input).expect("could not parse input");
+
+        let commands = "b 2\nc\ns 2\nset a 0o7\nbogus\nq\ns\n";
+        let mut out = Vec::new();
+        Debugger::new(&problem)
+            .run(commands.as_bytes(), &mut out, false)
+            .unwrap();
+        let out = String::from_utf8(out).unwrap();
+
+        assert!(out.contains("hit breakpoint at 2"));
+        assert!(out.contains("output: 4\n"));
+        assert!(out.contains("A = 7 "));
+        assert!(out.contains("unknown command \"bogus\""));
+        // nothing after quitting is run
+        assert_eq!(out.matches("chronospatial computer, step").count(), 6);
+    }
+
+    #[test]
+    fn malformed_input() {
+        let input = "Register A: 1\nRegister B: 0\nRegister C\n\nProgram: 0,1\n";
+        let err = ChronospatialComputer::from_str(input).unwrap_err();
+        assert_eq!((err.line, err.column), (3, 1));
+
+        let input = "Register A: 1\nRegister B: 0\nRegister C: 0\n\nProgram: 0,1,5\n";
+        let err = ChronospatialComputer::from_str(input).unwrap_err();
+        assert_eq!((err.line, err.column), (5, 14));
+        assert_eq!(err.message, "instruction is missing its operand");
+    }
 }
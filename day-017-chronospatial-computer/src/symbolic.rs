@@ -0,0 +1,290 @@
+//! Symbolic evaluation of a program's loop, for solving quine-style programs.
+//!
+//! Programs of the shape used by the puzzle are a single loop that ends with
+//! `jnz 0`. Each time around the loop, A is shifted right by a fixed number
+//! of bits `k`, B and C are recomputed from scratch, and some values are
+//! output. Every output of the `i`th iteration is then a function of
+//! `A >> (k * i)` alone, so a value of A that makes the program output a
+//! given sequence can be built `k` bits at a time, starting from the last
+//! output.
+//!
+//! [`ChronospatialComputer::analyse`](crate::ChronospatialComputer::analyse)
+//! checks that a program has this shape, reporting why it can't be solved if
+//! not, and expresses each output as an [`Expr`] of A.
+use std::{fmt, ops::Range};
+
+use anyhow::{bail, Result};
+
+use crate::vm::{Op, Operand};
+
+/// A symbolic register value at some point in one iteration of the loop.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Expr {
+    /// The value of register A at the start of the iteration.
+    A,
+    /// The value of register B or C at the start of the iteration, which is
+    /// left over from the previous iteration.
+    Carried(char),
+    Const(u64),
+    Xor(Box<Expr>, Box<Expr>),
+    /// The low bits of a value, masked with the constant.
+    And(Box<Expr>, u64),
+    /// A value shifted right by another, which is the same as dividing by
+    /// the corresponding power of two.
+    Shr(Box<Expr>, Box<Expr>),
+}
+
+impl Expr {
+    fn xor(self, other: Self) -> Self {
+        match (self, other) {
+            (Self::Const(x), Self::Const(y)) => Self::Const(x ^ y),
+            (x, Self::Const(0)) | (Self::Const(0), x) => x,
+            (x, y) => Self::Xor(Box::new(x), Box::new(y)),
+        }
+    }
+
+    fn and(self, mask: u64) -> Self {
+        match self {
+            Self::Const(x) => Self::Const(x & mask),
+            Self::And(x, m) => Self::And(x, m & mask),
+            x => Self::And(Box::new(x), mask),
+        }
+    }
+
+    fn shr(self, amount: Self) -> Self {
+        match (self, amount) {
+            (x, Self::Const(0)) => x,
+            (Self::Const(x), Self::Const(s)) => Self::Const(x.checked_shr(s as u32).unwrap_or(0)),
+            (Self::Shr(x, s1), Self::Const(s2)) => match *s1 {
+                Self::Const(s1) => Self::Shr(x, Box::new(Self::Const(s1.saturating_add(s2)))),
+                s1 => Self::Shr(
+                    Box::new(Self::Shr(x, Box::new(s1))),
+                    Box::new(Self::Const(s2)),
+                ),
+            },
+            (x, s) => Self::Shr(Box::new(x), Box::new(s)),
+        }
+    }
+
+    /// Evaluates the expression for the given value of A.
+    ///
+    /// # Panics
+    ///
+    /// If the expression depends on a carried register.
+    pub fn eval(&self, a: u64) -> u64 {
+        match self {
+            Self::A => a,
+            Self::Carried(r) => panic!("cannot evaluate carried register {}", r),
+            Self::Const(x) => *x,
+            Self::Xor(x, y) => x.eval(a) ^ y.eval(a),
+            Self::And(x, mask) => x.eval(a) & mask,
+            Self::Shr(x, s) => {
+                let s = s.eval(a);
+                u32::try_from(s)
+                    .ok()
+                    .and_then(|s| x.eval(a).checked_shr(s))
+                    .unwrap_or(0)
+            }
+        }
+    }
+
+    fn is_carried(&self) -> bool {
+        match self {
+            Self::Carried(_) => true,
+            Self::A | Self::Const(_) => false,
+            Self::Xor(x, y) | Self::Shr(x, y) => x.is_carried() || y.is_carried(),
+            Self::And(x, _) => x.is_carried(),
+        }
+    }
+
+    /// The smallest and largest values the expression can take.
+    fn bounds(&self) -> (u64, u64) {
+        match self {
+            Self::A | Self::Carried(_) => (0, u64::MAX),
+            Self::Const(x) => (*x, *x),
+            Self::Xor(x, y) => {
+                let hi = x.bounds().1 | y.bounds().1;
+                (0, u64::MAX >> hi.leading_zeros())
+            }
+            Self::And(x, mask) => (0, x.bounds().1.min(*mask)),
+            Self::Shr(x, s) => (0, x.bounds().1 >> s.bounds().0.min(63)),
+        }
+    }
+
+    /// The bits of A that can affect the given bits of the expression's
+    /// value, or `None` if they don't depend on A.
+    pub fn dependencies(&self, bits: Range<u32>) -> Option<Range<u32>> {
+        if bits.is_empty() {
+            return None;
+        }
+
+        match self {
+            Self::A => Some(bits),
+            Self::Carried(_) | Self::Const(_) => None,
+            Self::Xor(x, y) => union(x.dependencies(bits.clone()), y.dependencies(bits)),
+            Self::And(x, mask) => {
+                let width = u64::BITS - mask.leading_zeros();
+                x.dependencies(bits.start..bits.end.min(width))
+            }
+            Self::Shr(x, s) => {
+                let (lo, hi) = s.bounds();
+                let (lo, hi) = (lo.min(64) as u32, hi.min(64) as u32);
+                let shifted = x.dependencies(bits.start + lo..(bits.end + hi).min(64));
+                union(shifted, s.dependencies(0..64))
+            }
+        }
+    }
+}
+
+fn union(a: Option<Range<u32>>, b: Option<Range<u32>>) -> Option<Range<u32>> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(a.start.min(b.start)..a.end.max(b.end)),
+        (a, b) => a.or(b),
+    }
+}
+
+impl fmt::Display for Expr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::A => write!(f, "A"),
+            Self::Carried(r) => write!(f, "{}'", r),
+            Self::Const(x) => write!(f, "{}", x),
+            Self::Xor(x, y) => write!(f, "({} ^ {})", x, y),
+            Self::And(x, mask) => write!(f, "({} & {})", x, mask),
+            Self::Shr(x, s) => write!(f, "({} >> {})", x, s),
+        }
+    }
+}
+
+/// The result of analysing a program's loop.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Analysis {
+    /// The number of bits A is shifted right by each iteration.
+    pub shift: u32,
+    /// The values output by each iteration, as functions of A at the start
+    /// of the iteration.
+    pub outputs: Vec<Expr>,
+}
+
+/// Symbolically evaluates one iteration of the program's loop, or explains
+/// why the program doesn't have a shape that [`Analysis::solve`] can handle.
+pub(crate) fn analyse(ops: &[Op]) -> Result<Analysis> {
+    let Some((Op::Jnz(Operand::Literal(0)), body)) = ops.split_last() else {
+        bail!("the program does not end with a jump back to the start");
+    };
+
+    let (mut a, mut b, mut c) = (Expr::A, Expr::Carried('B'), Expr::Carried('C'));
+    let mut outputs = Vec::default();
+
+    for (ip, op) in body.iter().enumerate() {
+        let combo = |x: &Operand| match x {
+            Operand::Literal(v) => Expr::Const(*v as u64),
+            Operand::RegisterA => a.clone(),
+            Operand::RegisterB => b.clone(),
+            Operand::RegisterC => c.clone(),
+        };
+
+        match op {
+            Op::Adv(x) => a = a.clone().shr(combo(x)),
+            Op::Bxl(x) => b = b.clone().xor(combo(x)),
+            Op::Bst(x) => b = combo(x).and(7),
+            Op::Jnz(_) => bail!("the jump at {} is not the end of the loop", ip),
+            Op::Bxc(_) => b = b.clone().xor(c.clone()),
+            Op::Out(x) => outputs.push(combo(x).and(7)),
+            Op::Bdv(x) => b = a.clone().shr(combo(x)),
+            Op::Cdv(x) => c = a.clone().shr(combo(x)),
+        }
+    }
+
+    let shift = match a {
+        Expr::Shr(ref x, ref s) if **x == Expr::A => match **s {
+            Expr::Const(s @ 1..=63) => s as u32,
+            _ => bail!("A is not shifted by a constant each iteration: {}", a),
+        },
+        _ => bail!("A is not shifted by a constant each iteration: {}", a),
+    };
+
+    if outputs.is_empty() {
+        bail!("the loop does not output anything");
+    }
+
+    if let Some(out) = outputs.iter().find(|x| x.is_carried()) {
+        bail!("an output depends on the previous iteration: {}", out);
+    }
+
+    Ok(Analysis { shift, outputs })
+}
+
+impl Analysis {
+    /// The bits of A at the start of an iteration that its outputs depend
+    /// on.
+    pub fn window(&self) -> Option<Range<u32>> {
+        self.outputs
+            .iter()
+            .map(|x| x.dependencies(0..3))
+            .fold(None, union)
+    }
+
+    /// Finds the smallest value of A for which the program outputs exactly
+    /// `expected`, if there is one.
+    pub fn solve(&self, expected: &[u8]) -> Option<u64> {
+        let per_iteration = self.outputs.len();
+        if expected.is_empty() || !expected.len().is_multiple_of(per_iteration) {
+            return None;
+        }
+
+        let iterations = expected.chunks(per_iteration).collect::<Vec<_>>();
+        self.solve_helper(0, &iterations)
+    }
+
+    /// Chooses the next `shift` bits of A, below `acc`, for the last of the
+    /// remaining iterations.
+    fn solve_helper(&self, acc: u64, iterations: &[&[u8]]) -> Option<u64> {
+        let Some((last, rest)) = iterations.split_last() else {
+            return Some(acc);
+        };
+
+        // the final iteration must leave A at 0, and the ones before it
+        // must not
+        let first = if acc == 0 && !rest.is_empty() { 1 } else { 0 };
+        let base = acc.checked_mul(1 << self.shift)?;
+
+        for bits in first..(1 << self.shift) {
+            let a = base | bits;
+            if self
+                .outputs
+                .iter()
+                .zip(last.iter())
+                .all(|(x, expected)| x.eval(a) == *expected as u64)
+            {
+                if let Some(ret) = self.solve_helper(a, rest) {
+                    return Some(ret);
+                }
+            }
+        }
+
+        None
+    }
+}
+
+impl fmt::Display for Analysis {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "each iteration shifts A right by {} bits", self.shift)?;
+        if let Some(window) = self.window() {
+            writeln!(
+                f,
+                "the outputs depend on bits {}..{} of A at the start of the iteration",
+                window.start, window.end
+            )?;
+        }
+
+        for (i, out) in self.outputs.iter().enumerate() {
+            write!(f, "out[{}] = {}", i, out)?;
+            if i + 1 < self.outputs.len() {
+                writeln!(f)?;
+            }
+        }
+
+        Ok(())
+    }
+}
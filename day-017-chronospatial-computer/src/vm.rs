@@ -0,0 +1,518 @@
+//! The chronospatial computer as a reusable virtual machine.
+//!
+//! A [`Program`] is a list of [`Op`]s, which can be decoded from the puzzle's
+//! 3-bit codes or assembled from mnemonic text, and a [`Vm`] runs it with the
+//! same semantics as the puzzle:
+//!
+//! ```
+//! use chronospatial_computer::vm::{Program, Vm, VmError};
+//!
+//! let program: Program = "
+//!     adv 1    ; A = A >> 1
+//!     out A
+//!     jnz 0
+//! ".parse().unwrap();
+//! assert_eq!(program.codes(), vec![0, 1, 5, 4, 3, 0]);
+//!
+//! let mut vm = Vm::new(program.clone()).with_registers(729, 0, 0);
+//! assert_eq!(vm.run().unwrap(), vec![4, 6, 3, 5, 6, 3, 5, 2, 1, 0]);
+//!
+//! // with only 4 bits, A runs out sooner
+//! let mut vm = Vm::new(program.clone()).with_registers(729, 0, 0).width(4);
+//! assert_eq!(vm.outputs().collect::<Result<Vec<_>, _>>().unwrap(), vec![4, 2, 1, 0]);
+//!
+//! // a program that never halts
+//! let mut vm = Vm::new("out A\njnz 0".parse().unwrap()).with_registers(1, 0, 0).limit(100);
+//! assert_eq!(vm.run(), Err(VmError::Limit(100)));
+//! ```
+use std::{fmt, str::FromStr};
+
+use anyhow::bail;
+use aoc_plumbing::ParseError;
+
+/// A single instruction, with its operand.
+///
+/// Operands of `bxl`, `jnz` and `bxc` are always [`Operand::Literal`]. The
+/// others take combo operands, where the codes 4 to 6 refer to registers.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Op {
+    Adv(Operand),
+    Bxl(Operand),
+    Bst(Operand),
+    Jnz(Operand),
+    Bxc(Operand),
+    Out(Operand),
+    Bdv(Operand),
+    Cdv(Operand),
+}
+
+impl TryFrom<(u8, u8)> for Op {
+    type Error = anyhow::Error;
+
+    fn try_from(value: (u8, u8)) -> Result<Self, Self::Error> {
+        if value.1 > 7 {
+            bail!("invalid operand");
+        }
+
+        let ret = match value.0 {
+            0 => Self::Adv(value.1.try_into()?),
+            1 => Self::Bxl(Operand::Literal(value.1)),
+            2 => Self::Bst(value.1.try_into()?),
+            3 => Self::Jnz(Operand::Literal(value.1)),
+            4 => Self::Bxc(Operand::Literal(value.1)),
+            5 => Self::Out(value.1.try_into()?),
+            6 => Self::Bdv(value.1.try_into()?),
+            7 => Self::Cdv(value.1.try_into()?),
+            _ => bail!("invalid op"),
+        };
+
+        Ok(ret)
+    }
+}
+
+impl fmt::Display for Op {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Adv(x) => write!(f, "adv {}", x),
+            Self::Bxl(x) => write!(f, "bxl {}", x),
+            Self::Bst(x) => write!(f, "bst {}", x),
+            Self::Jnz(x) => write!(f, "jnz {}", x),
+            Self::Bxc(Operand::Literal(0)) => write!(f, "bxc"),
+            Self::Bxc(x) => write!(f, "bxc {}", x),
+            Self::Out(x) => write!(f, "out {}", x),
+            Self::Bdv(x) => write!(f, "bdv {}", x),
+            Self::Cdv(x) => write!(f, "cdv {}", x),
+        }
+    }
+}
+
+impl Op {
+    /// The instruction's opcode and operand codes.
+    pub fn codes(&self) -> (u8, u8) {
+        match self {
+            Self::Adv(x) => (0, x.code()),
+            Self::Bxl(x) => (1, x.code()),
+            Self::Bst(x) => (2, x.code()),
+            Self::Jnz(x) => (3, x.code()),
+            Self::Bxc(x) => (4, x.code()),
+            Self::Out(x) => (5, x.code()),
+            Self::Bdv(x) => (6, x.code()),
+            Self::Cdv(x) => (7, x.code()),
+        }
+    }
+
+    /// What the instruction does, as pseudocode.
+    pub fn pseudocode(&self) -> String {
+        match self {
+            Self::Adv(x) => format!("A = A >> {}", x),
+            Self::Bxl(x) => format!("B = B ^ {}", x),
+            Self::Bst(x) => format!("B = {} & 7", x),
+            Self::Jnz(x) => format!("if A != 0 goto {}", x.code() / 2),
+            Self::Bxc(_) => "B = B ^ C".to_string(),
+            Self::Out(x) => format!("out {} & 7", x),
+            Self::Bdv(x) => format!("B = A >> {}", x),
+            Self::Cdv(x) => format!("C = A >> {}", x),
+        }
+    }
+
+    /// Whether the operand is a combo operand, which may refer to a
+    /// register.
+    fn takes_combo(mnemonic: &str) -> Option<bool> {
+        match mnemonic {
+            "adv" | "bst" | "out" | "bdv" | "cdv" => Some(true),
+            "bxl" | "jnz" | "bxc" => Some(false),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Operand {
+    Literal(u8),
+    RegisterA,
+    RegisterB,
+    RegisterC,
+}
+
+impl fmt::Display for Operand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Literal(x) => write!(f, "{}", x),
+            Self::RegisterA => write!(f, "A"),
+            Self::RegisterB => write!(f, "B"),
+            Self::RegisterC => write!(f, "C"),
+        }
+    }
+}
+
+impl Operand {
+    /// The operand's code.
+    pub fn code(&self) -> u8 {
+        match self {
+            Self::Literal(x) => *x,
+            Self::RegisterA => 4,
+            Self::RegisterB => 5,
+            Self::RegisterC => 6,
+        }
+    }
+}
+
+impl TryFrom<u8> for Operand {
+    type Error = anyhow::Error;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        let ret = match value {
+            0..=3 => Self::Literal(value),
+            4 => Self::RegisterA,
+            5 => Self::RegisterB,
+            6 => Self::RegisterC,
+            _ => bail!("invalid operand"),
+        };
+
+        Ok(ret)
+    }
+}
+
+/// A sequence of instructions.
+///
+/// Parsing a program assembles it from text with one instruction per line,
+/// such as `bst A` or `jnz 0`. Mnemonics and registers are case-insensitive,
+/// anything after a `;` or `#` is a comment, and a line may start with the
+/// instruction's index, so the output of [`Program::disassemble`] can be
+/// assembled again. As in the puzzle, the operand of `jnz` is the index of
+/// the code to jump to, which is twice the index of the instruction.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct Program {
+    ops: Vec<Op>,
+}
+
+impl Program {
+    pub fn new(ops: Vec<Op>) -> Self {
+        Self { ops }
+    }
+
+    /// Decodes a program from its 3-bit codes.
+    pub fn from_codes(codes: &[u8]) -> anyhow::Result<Self> {
+        if !codes.len().is_multiple_of(2) {
+            bail!("instruction is missing its operand");
+        }
+
+        codes
+            .chunks_exact(2)
+            .map(|c| Op::try_from((c[0], c[1])))
+            .collect::<Result<Vec<_>, _>>()
+            .map(Self::new)
+    }
+
+    pub fn ops(&self) -> &[Op] {
+        &self.ops
+    }
+
+    /// The program's 3-bit codes.
+    pub fn codes(&self) -> Vec<u8> {
+        self.ops
+            .iter()
+            .flat_map(|op| {
+                let (op, operand) = op.codes();
+                [op, operand]
+            })
+            .collect()
+    }
+
+    /// The program as assembly, one instruction per line, prefixed with its
+    /// index and followed by pseudocode for what it does.
+    pub fn disassemble(&self) -> String {
+        self.ops
+            .iter()
+            .enumerate()
+            .map(|(ip, op)| format!("{}\n", disassemble_line(ip, op)))
+            .collect()
+    }
+}
+
+/// Formats a single instruction for [`Program::disassemble`].
+pub(crate) fn disassemble_line(ip: usize, op: &Op) -> String {
+    format!("{:>3}  {:<8}; {}", ip, op.to_string(), op.pseudocode())
+}
+
+impl fmt::Display for Program {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for op in self.ops.iter() {
+            writeln!(f, "{}", op)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for Program {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut ops = Vec::default();
+
+        for line in s.lines() {
+            let code = line.split([';', '#']).next().unwrap_or_default();
+            let mut words = code.split_whitespace().peekable();
+
+            if words
+                .peek()
+                .is_some_and(|w| w.bytes().all(|b| b.is_ascii_digit()))
+            {
+                words.next();
+            }
+
+            let Some(mnemonic) = words.next() else {
+                continue;
+            };
+            let operand = words.next();
+            if let Some(extra) = words.next() {
+                return Err(ParseError::at(s, extra, "unexpected operand"));
+            }
+
+            ops.push(assemble(s, mnemonic, operand)?);
+        }
+
+        Ok(Self::new(ops))
+    }
+}
+
+/// Assembles one instruction, where `mnemonic` and `operand` are subslices
+/// of `input`.
+fn assemble(input: &str, mnemonic: &str, operand: Option<&str>) -> Result<Op, ParseError> {
+    let lower = mnemonic.to_ascii_lowercase();
+    let combo = Op::takes_combo(&lower)
+        .ok_or_else(|| ParseError::at(input, mnemonic, "unknown instruction"))?;
+
+    let operand = match (operand, lower.as_str()) {
+        (None, "bxc") => Operand::Literal(0),
+        (None, _) => return Err(ParseError::at(input, mnemonic, "missing operand")),
+        (Some(x), _) => match (x.to_ascii_uppercase().as_str(), x.parse::<u8>()) {
+            ("A", _) if combo => Operand::RegisterA,
+            ("B", _) if combo => Operand::RegisterB,
+            ("C", _) if combo => Operand::RegisterC,
+            (_, Ok(v @ 0..=3)) => Operand::Literal(v),
+            (_, Ok(v @ 4..=7)) if !combo => Operand::Literal(v),
+            (_, Ok(4..=7)) => {
+                return Err(ParseError::at(
+                    input,
+                    x,
+                    "combo literals must be 0 to 3; use A, B or C for registers",
+                ))
+            }
+            _ => return Err(ParseError::at(input, x, "invalid operand")),
+        },
+    };
+
+    let ret = match lower.as_str() {
+        "adv" => Op::Adv(operand),
+        "bxl" => Op::Bxl(operand),
+        "bst" => Op::Bst(operand),
+        "jnz" => Op::Jnz(operand),
+        "bxc" => Op::Bxc(operand),
+        "out" => Op::Out(operand),
+        "bdv" => Op::Bdv(operand),
+        _ => Op::Cdv(operand),
+    };
+
+    Ok(ret)
+}
+
+/// The result of executing a single instruction.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Step {
+    Continue,
+    Output(u8),
+    Halt,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, thiserror::Error)]
+pub enum VmError {
+    #[error("the program did not halt within {0} instructions")]
+    Limit(usize),
+}
+
+/// Runs a [`Program`].
+///
+/// Registers are `width` bits wide, 64 by default, and every value written to
+/// them is truncated to that many bits. The instruction pointer is an index
+/// into the program's instructions rather than its codes.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Vm {
+    program: Program,
+    a: u64,
+    b: u64,
+    c: u64,
+    ip: usize,
+    mask: u64,
+    limit: Option<usize>,
+    executed: usize,
+}
+
+impl Vm {
+    pub fn new(program: Program) -> Self {
+        Self {
+            program,
+            a: 0,
+            b: 0,
+            c: 0,
+            ip: 0,
+            mask: u64::MAX,
+            limit: None,
+            executed: 0,
+        }
+    }
+
+    /// Sets the registers A, B and C.
+    pub fn with_registers(mut self, a: u64, b: u64, c: u64) -> Self {
+        self.set_registers(a, b, c);
+        self
+    }
+
+    /// Sets the number of bits in each register, truncating their current
+    /// values.
+    ///
+    /// # Panics
+    ///
+    /// If `bits` is not between 1 and 64.
+    pub fn width(mut self, bits: u32) -> Self {
+        assert!((1..=64).contains(&bits), "invalid register width {}", bits);
+        self.mask = u64::MAX >> (64 - bits);
+        let (a, b, c) = self.registers();
+        self.set_registers(a, b, c);
+        self
+    }
+
+    /// Makes [`Vm::step`] fail once this many instructions have been
+    /// executed without halting.
+    pub fn limit(mut self, instructions: usize) -> Self {
+        self.limit = Some(instructions);
+        self
+    }
+
+    pub fn program(&self) -> &Program {
+        &self.program
+    }
+
+    /// The registers A, B and C.
+    pub fn registers(&self) -> (u64, u64, u64) {
+        (self.a, self.b, self.c)
+    }
+
+    /// Sets the registers A, B and C, truncated to the register width.
+    pub fn set_registers(&mut self, a: u64, b: u64, c: u64) {
+        self.a = a & self.mask;
+        self.b = b & self.mask;
+        self.c = c & self.mask;
+    }
+
+    /// The index of the next instruction to execute.
+    pub fn ip(&self) -> usize {
+        self.ip
+    }
+
+    /// The number of instructions executed so far.
+    pub fn executed(&self) -> usize {
+        self.executed
+    }
+
+    /// Whether the instruction pointer is past the end of the program.
+    pub fn is_halted(&self) -> bool {
+        self.ip >= self.program.ops.len()
+    }
+
+    /// Executes the instruction at the instruction pointer, if there is one.
+    pub fn step(&mut self) -> Result<Step, VmError> {
+        let Some(op) = self.program.ops.get(self.ip) else {
+            return Ok(Step::Halt);
+        };
+
+        if let Some(limit) = self.limit {
+            if self.executed >= limit {
+                return Err(VmError::Limit(limit));
+            }
+        }
+        self.executed += 1;
+
+        let mut ret = Step::Continue;
+        match *op {
+            Op::Adv(x) => self.a = self.divide(x),
+            Op::Bxl(x) => self.b ^= self.value(x),
+            Op::Bst(x) => self.b = self.value(x) % 8,
+            Op::Jnz(x) => {
+                if self.a != 0 {
+                    self.ip = self.value(x) as usize / 2;
+                    return Ok(ret);
+                }
+            }
+            Op::Bxc(_) => self.b ^= self.c,
+            Op::Out(x) => ret = Step::Output((self.value(x) % 8) as u8),
+            Op::Bdv(x) => self.b = self.divide(x),
+            Op::Cdv(x) => self.c = self.divide(x),
+        }
+
+        self.b &= self.mask;
+        self.ip += 1;
+        Ok(ret)
+    }
+
+    /// Runs until the program halts, returning everything it output.
+    pub fn run(&mut self) -> Result<Vec<u8>, VmError> {
+        self.outputs().collect()
+    }
+
+    /// An iterator over the values output as the program runs, which ends
+    /// when the program halts or after the first error.
+    pub fn outputs(&mut self) -> Outputs<'_> {
+        Outputs {
+            vm: self,
+            failed: false,
+        }
+    }
+
+    fn value(&self, operand: Operand) -> u64 {
+        match operand {
+            Operand::Literal(x) => x as u64,
+            Operand::RegisterA => self.a,
+            Operand::RegisterB => self.b,
+            Operand::RegisterC => self.c,
+        }
+    }
+
+    /// A divided by 2 to the power of the operand, which is 0 if the power
+    /// doesn't fit in a `u64`.
+    fn divide(&self, operand: Operand) -> u64 {
+        u32::try_from(self.value(operand))
+            .ok()
+            .and_then(|x| self.a.checked_shr(x))
+            .unwrap_or(0)
+    }
+}
+
+/// The values output by a [`Vm`], from [`Vm::outputs`].
+pub struct Outputs<'a> {
+    vm: &'a mut Vm,
+    failed: bool,
+}
+
+impl Iterator for Outputs<'_> {
+    type Item = Result<u8, VmError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed {
+            return None;
+        }
+
+        loop {
+            match self.vm.step() {
+                Ok(Step::Continue) => (),
+                Ok(Step::Output(x)) => return Some(Ok(x)),
+                Ok(Step::Halt) => return None,
+                Err(e) => {
+                    self.failed = true;
+                    return Some(Err(e));
+                }
+            }
+        }
+    }
+}
@@ -1,8 +1,11 @@
-use std::{collections::VecDeque, str::FromStr};
+use std::str::FromStr;
 
 use anyhow::anyhow;
-use aoc_common::grid::{Coordinate, Grid};
-use aoc_plumbing::Problem;
+use aoc_common::{
+    grid::{Coordinate, Grid},
+    pathfinding,
+};
+use aoc_plumbing::{ParseError, ParseErrorContext, Problem};
 
 #[derive(Debug, Clone)]
 pub struct RamRun {
@@ -10,7 +13,7 @@ pub struct RamRun {
 }
 
 impl FromStr for RamRun {
-    type Err = anyhow::Error;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let mut coords = Vec::default();
@@ -18,8 +21,11 @@ impl FromStr for RamRun {
         for l in s.lines() {
             if let Some((left, right)) = l.split_once(',') {
                 coords.push(Coordinate::from((
-                    right.parse::<isize>()?,
-                    left.parse::<isize>()?,
+                    right
+                        .parse::<isize>()
+                        .parse_err_at(s, right, "invalid coordinate")?,
+                    left.parse::<isize>()
+                        .parse_err_at(s, left, "invalid coordinate")?,
                 )));
             }
         }
@@ -33,32 +39,18 @@ impl RamRun {
         let mut grid = Grid::new(size, size, '.');
         self.coords.iter().take(len).for_each(|x| grid[*x] = '#');
 
-        let mut q = VecDeque::default();
-        let mut visited = Grid::new(size, size, false);
         let start = Coordinate::from((0_isize, 0_isize));
         let end = Coordinate::from((size - 1, size - 1));
-        q.push_back((start, 0));
 
-        while let Some((cur, dist)) = q.pop_front() {
-            if visited[cur] {
-                continue;
-            }
-
-            if cur == end {
-                return Some(dist);
-            }
-
-            visited[cur] = true;
-
-            q.extend(
-                cur.cardinal_neighbours()
-                    .into_iter()
-                    .filter(|x| grid.get(*x).is_some_and(|y| y == '.'))
-                    .map(|x| (x, dist + 1)),
-            );
-        }
-
-        None
+        pathfinding::bfs(
+            &start,
+            |cur| {
+                grid.cardinal_neighbours(*cur)
+                    .filter(|(_, y)| **y == '.')
+                    .map(|(x, _)| x)
+            },
+            |cur| *cur == end,
+        )
     }
 
     fn part2(&self, size: usize) -> Option<Coordinate> {
@@ -100,12 +92,16 @@ impl Problem for RamRun {
     }
 }
 
+aoc_plumbing::register_problem!(RamRun);
+
 #[cfg(test)]
 mod tests {
     use aoc_plumbing::Solution;
 
     use super::*;
 
+    aoc_plumbing::answers_test!(RamRun);
+
     #[test]
     #[ignore]
     fn full_dataset() {
@@ -1,7 +1,6 @@
 use std::str::FromStr;
 
-use anyhow::{anyhow, Ok};
-use aoc_plumbing::Problem;
+use aoc_plumbing::{ParseError, ParseErrorContext, Problem};
 use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
 use rustc_hash::{FxHashMap, FxHashSet};
 
@@ -11,12 +10,14 @@ pub struct LinenLayout {
 }
 
 impl FromStr for LinenLayout {
-    type Err = anyhow::Error;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (top, bottom) = s
-            .split_once("\n\n")
-            .ok_or_else(|| anyhow!("invalid input"))?;
+        let (top, bottom) = s.split_once("\n\n").parse_err_at(
+            s,
+            &s[s.len()..],
+            "expected a blank line before the designs",
+        )?;
         let patterns = top.split(", ").map(|x| x.to_owned()).collect();
 
         let lines = bottom.lines().collect::<Vec<_>>();
@@ -91,12 +92,16 @@ impl Problem for LinenLayout {
     }
 }
 
+aoc_plumbing::register_problem!(LinenLayout);
+
 #[cfg(test)]
 mod tests {
     use aoc_plumbing::Solution;
 
     use super::*;
 
+    aoc_plumbing::answers_test!(LinenLayout);
+
     #[test]
     #[ignore]
     fn full_dataset() {
@@ -1,114 +1,106 @@
 use std::str::FromStr;
 
-use anyhow::anyhow;
 use aoc_common::{
-    direction::Cardinal,
     grid::{Coordinate, Grid},
+    pathfinding,
 };
-use aoc_plumbing::Problem;
+use aoc_plumbing::{ParseError, ParseErrorContext, Problem};
 use rayon::iter::IntoParallelIterator;
 use rayon::iter::ParallelIterator;
 
 #[derive(Debug, Clone)]
 pub struct RaceCondition {
-    grid: Grid<char>,
-    start: Coordinate,
-    end: Coordinate,
-    acc: Grid<usize>,
-    path: Vec<Coordinate>,
+    from_start: Grid<usize>,
+    to_end: Grid<usize>,
+    length: usize,
 }
 
 impl FromStr for RaceCondition {
-    type Err = anyhow::Error;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let grid = Grid::from_str(s)?;
-        let start = grid
-            .find_coordinate(|x| *x == 'S')
-            .ok_or_else(|| anyhow!("could not find start"))?;
+        let grid: Grid<char> =
+            Grid::from_str(s).map_err(|e| ParseError::at_line(s, e.row(), e.to_string()))?;
+        let start =
+            grid.find_coordinate(|x| *x == 'S')
+                .parse_err_at(s, s, "could not find start")?;
         let end = grid
             .find_coordinate(|x| *x == 'E')
-            .ok_or_else(|| anyhow!("could not find end"))?;
-        let acc = Grid::new(grid.n, grid.m, usize::MAX);
-        let path = Vec::default();
+            .parse_err_at(s, s, "could not find end")?;
+
+        let from_start = Self::distances(&grid, start);
+        let to_end = Self::distances(&grid, end);
+        let length = from_start
+            .get(end)
+            .filter(|d| *d != usize::MAX)
+            .parse_err_at(s, s, "the end is not reachable from the start")?;
 
         Ok(Self {
-            grid,
-            start,
-            end,
-            acc,
-            path,
+            from_start,
+            to_end,
+            length,
         })
     }
 }
 
 impl RaceCondition {
-    fn part1(&mut self, threshold: usize) -> Result<usize, anyhow::Error> {
-        let mut ret = 0;
-
-        let mut dist = 0;
-        let mut cur = self.start;
-
-        loop {
-            self.acc[cur] = dist;
-            self.path.push(cur);
-            dist += 1;
-            ret += self.short_cheats(&cur, threshold);
-
-            if cur == self.end {
-                break;
-            }
-
-            cur = *cur
-                .cardinal_neighbours()
-                .iter()
-                .find(|&x| {
-                    self.acc.get(*x).is_some_and(|n| n == usize::MAX)
-                        && self.grid.get(*x).is_some_and(|c| c == '.' || c == 'E')
-                })
-                .ok_or_else(|| anyhow!("invalid path"))?;
+    /// The distance from `source` to every cell of the track, or `usize::MAX`
+    /// for walls and unreachable cells.
+    fn distances(grid: &Grid<char>, source: Coordinate) -> Grid<usize> {
+        let successors = |x: &Coordinate| {
+            grid.cardinal_neighbours(*x)
+                .filter(|(_, c)| **c != '#')
+                .map(|(n, _)| (n, 1))
+        };
+
+        let mut ret = Grid::new(grid.n, grid.m, usize::MAX);
+        for (coord, (dist, _)) in pathfinding::dijkstra_all(&source, successors) {
+            ret[coord] = dist as usize;
         }
-
-        Ok(ret)
+        ret
     }
 
-    fn long_cheats(&self, threshold: usize) -> usize {
-        (1..self.path.len())
+    /// Counts the cheats of up to `max_len` steps that save at least
+    /// `threshold` steps. A cheat from `a` to `b` takes the distance to `a`,
+    /// plus the manhattan distance from `a` to `b`, plus the distance from `b`
+    /// to the end.
+    fn cheats(&self, max_len: usize, threshold: usize) -> usize {
+        let (n, m) = (self.from_start.n, self.from_start.m);
+        let max_len = max_len as isize;
+
+        (0..n)
             .into_par_iter()
             .map(|i| {
-                let b = self.path[i];
-                self.path[0..i]
-                    .iter()
-                    .filter(|&a| {
-                        let d = a.manhattan_distance(&b);
-                        let saved = self.acc[*a].abs_diff(self.acc[b]);
-                        d <= 20 && saved >= d && saved - d >= threshold
-                    })
-                    .count()
+                let mut count = 0;
+
+                for j in 0..m {
+                    let a = Coordinate::from((i, j));
+                    let before = self.from_start[a];
+                    if before == usize::MAX {
+                        continue;
+                    }
+
+                    for di in -max_len..=max_len {
+                        let rem = max_len - di.abs();
+                        for dj in -rem..=rem {
+                            let b = Coordinate(a.0 + di, a.1 + dj);
+                            let Some(after) = self.to_end.get(b).filter(|d| *d != usize::MAX)
+                            else {
+                                continue;
+                            };
+
+                            let len = before + (di.abs() + dj.abs()) as usize + after;
+                            if len + threshold <= self.length {
+                                count += 1;
+                            }
+                        }
+                    }
+                }
+
+                count
             })
             .sum()
     }
-
-    fn short_cheats(&self, coord: &Coordinate, threshold: usize) -> usize {
-        let dist = self.acc[*coord];
-
-        let dirs = Cardinal::all();
-        dirs.iter()
-            .filter(|&dir| {
-                self.grid
-                    .get(coord.neighbour(dir))
-                    .is_some_and(|c| c == '#')
-                    && self
-                        .grid
-                        .get(coord.neighbour(dir).neighbour(dir))
-                        .is_some_and(|c| c == '.' || c == 'E')
-                    && self
-                        .acc
-                        .get(coord.neighbour(dir).neighbour(dir))
-                        .is_some_and(|n| n < usize::MAX && dist - n - 2 >= threshold)
-            })
-            .count()
-    }
 }
 
 impl Problem for RaceCondition {
@@ -121,20 +113,24 @@ impl Problem for RaceCondition {
     type P2 = usize;
 
     fn part_one(&mut self) -> Result<Self::P1, Self::ProblemError> {
-        self.part1(100)
+        Ok(self.cheats(2, 100))
     }
 
     fn part_two(&mut self) -> Result<Self::P2, Self::ProblemError> {
-        Ok(self.long_cheats(100))
+        Ok(self.cheats(20, 100))
     }
 }
 
+aoc_plumbing::register_problem!(RaceCondition);
+
 #[cfg(test)]
 mod tests {
     use aoc_plumbing::Solution;
 
     use super::*;
 
+    aoc_plumbing::answers_test!(RaceCondition);
+
     #[test]
     #[ignore]
     fn full_dataset() {
@@ -146,8 +142,8 @@ mod tests {
     #[test]
     fn example() {
         let input = std::fs::read_to_string("example.txt").expect("Unable to load input");
-        let mut problem = RaceCondition::from_str(&input).unwrap();
-        assert_eq!(5, problem.part1(20).unwrap());
-        assert_eq!(41, problem.long_cheats(70));
+        let problem = RaceCondition::from_str(&input).unwrap();
+        assert_eq!(5, problem.cheats(2, 20));
+        assert_eq!(41, problem.cheats(20, 70));
     }
 }
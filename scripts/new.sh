@@ -1,8 +1,9 @@
 #!/bin/bash
 set -e
 
-# This bit of crazy generates the new crate and adds the relevant imports and
-# macro lines to the cli and benchmarking crates
+# This bit of crazy generates the new crate and adds it to the cli and
+# benchmarking crates. The cli discovers days from its Cargo.toml, so only the
+# benchmarks need their imports and macro lines patched in.
 
 cargo generate --path ./template --lib --name "$2" -d day="$1"
 
@@ -26,19 +27,10 @@ IMPORT_REPLACEMENT="use ${CRATE_NAME}::${STRUCT_NAME};\\n// import_marker"
 echo "Appending $EXPECTED to cli"
 echo "$EXPECTED = { path = \"../$DESIRED\" }" >> aoc-cli/Cargo.toml
 
-echo "Modifying cli.rs"
-if [[ $OSTYPE == 'darwin'* ]]; then
-    sed -i '' -e "s#// import_marker#$IMPORT_REPLACEMENT#" aoc-cli/src/cli.rs
-else
-    sed -i "s#// import_marker#$IMPORT_REPLACEMENT#" aoc-cli/src/cli.rs
-fi
+# ====== answers
 
-COMMAND_REPLACEMENT="(${STRUCT_NAME}, $1),\\n    // command_marker"
-if [[ $OSTYPE == 'darwin'* ]]; then
-    sed -i '' -e "s#// command_marker#$COMMAND_REPLACEMENT#" aoc-cli/src/cli.rs
-else
-    sed -i "s#// command_marker#$COMMAND_REPLACEMENT#" aoc-cli/src/cli.rs
-fi
+echo "Adding day $1 to answers.toml"
+printf '\n[%d]\n# part_one = \n# part_two = \n' "$1" >> answers.toml
 
 # ====== benchmarks
 
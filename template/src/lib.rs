@@ -1,12 +1,12 @@
 use std::str::FromStr;
 
-use aoc_plumbing::Problem;
+use aoc_plumbing::{ParseError, Problem};
 
 #[derive(Debug, Clone)]
 pub struct {{project-name|upper_camel_case}};
 
 impl FromStr for {{project-name|upper_camel_case}} {
-    type Err = anyhow::Error;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         Ok(Self)
@@ -31,12 +31,16 @@ impl Problem for {{project-name|upper_camel_case}} {
     }
 }
 
+aoc_plumbing::register_problem!({{project-name|upper_camel_case}});
+
 #[cfg(test)]
 mod tests {
     use aoc_plumbing::Solution;
 
     use super::*;
 
+    aoc_plumbing::answers_test!({{project-name|upper_camel_case}});
+
     #[test]
     #[ignore]
     fn full_dataset() {